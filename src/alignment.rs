@@ -0,0 +1,264 @@
+//! Support for arithmetic between two `TimeSeries`, such as income less expense
+
+// --- module use statements ---
+
+use Add;
+use Time;
+use TimeSeries;
+use TimeValue;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Sub;
+
+// --- module enum definitions ---
+
+/// How the times of two series are matched when combining them
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Alignment<V = f64> {
+    /// Only times present in both series
+    Inner,
+    /// Times present in either series, with the given value standing in for a missing one
+    Outer(V),
+    /// Times of the left series, each matched with the latest right value on or before it.
+    /// Left times before the first right time are dropped.
+    AsOf,
+}
+
+// --- module impl definitions ---
+
+impl<T, V> TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Copy
+{
+    /// Combines the values of `self` and `other` at matching times
+    ///
+    ///  * `other` - Series supplying the right-hand values
+    ///  * `alignment` - How times of the two series are matched
+    ///  * `f` - Combines a left and right value
+    ///  * return - Series of combined values
+    ///
+    pub fn combine<F>(&self,
+                      other: &TimeSeries<T, V>,
+                      alignment: Alignment<V>,
+                      f: F)
+                      -> TimeSeries<T, V>
+        where F: Fn(V, V) -> V
+    {
+        // custom <fn time_series_combine>
+
+        let (left, right) = (&**self, &**other);
+        let mut data = Vec::with_capacity(left.len().max(right.len()));
+        let (mut i, mut j) = (0, 0);
+        let push = |data: &mut Vec<TimeValue<T, V>>, time, a, b| {
+            data.push(TimeValue {
+                          time,
+                          value: f(a, b),
+                      })
+        };
+
+        match alignment {
+            Alignment::Inner => {
+                while i < left.len() && j < right.len() {
+                    if left[i].time < right[j].time {
+                        i += 1;
+                    } else if right[j].time < left[i].time {
+                        j += 1;
+                    } else {
+                        push(&mut data, left[i].time, left[i].value, right[j].value);
+                        i += 1;
+                        j += 1;
+                    }
+                }
+            }
+            Alignment::Outer(fill) => {
+                while i < left.len() || j < right.len() {
+                    if j == right.len() || (i < left.len() && left[i].time < right[j].time) {
+                        push(&mut data, left[i].time, left[i].value, fill);
+                        i += 1;
+                    } else if i == left.len() || right[j].time < left[i].time {
+                        push(&mut data, right[j].time, fill, right[j].value);
+                        j += 1;
+                    } else {
+                        push(&mut data, left[i].time, left[i].value, right[j].value);
+                        i += 1;
+                        j += 1;
+                    }
+                }
+            }
+            Alignment::AsOf => {
+                for tv in left {
+                    if let Some(found) = other.latest_on_or_before(tv.time) {
+                        push(&mut data, tv.time, tv.value, found.value);
+                    }
+                }
+            }
+        }
+
+        TimeSeries::from_sorted(data)
+
+        // end <fn time_series_combine>
+    }
+
+    /// Sum of `self` and `other` with times matched by `alignment`
+    pub fn add_aligned(&self,
+                       other: &TimeSeries<T, V>,
+                       alignment: Alignment<V>)
+                       -> TimeSeries<T, V>
+        where V: Add<Output = V>
+    {
+        self.combine(other, alignment, |a, b| a + b)
+    }
+
+    /// Difference of `self` and `other` with times matched by `alignment`
+    pub fn sub_aligned(&self,
+                       other: &TimeSeries<T, V>,
+                       alignment: Alignment<V>)
+                       -> TimeSeries<T, V>
+        where V: Sub<Output = V>
+    {
+        self.combine(other, alignment, |a, b| a - b)
+    }
+
+    /// Product of `self` and `other` with times matched by `alignment`
+    pub fn mul_aligned(&self,
+                       other: &TimeSeries<T, V>,
+                       alignment: Alignment<V>)
+                       -> TimeSeries<T, V>
+        where V: Mul<Output = V>
+    {
+        self.combine(other, alignment, |a, b| a * b)
+    }
+
+    /// Quotient of `self` and `other` with times matched by `alignment`
+    pub fn div_aligned(&self,
+                       other: &TimeSeries<T, V>,
+                       alignment: Alignment<V>)
+                       -> TimeSeries<T, V>
+        where V: Div<Output = V>
+    {
+        self.combine(other, alignment, |a, b| a / b)
+    }
+}
+
+/// Sum of two series at the times present in both
+impl<'b, T, V> Add<&'b TimeSeries<T, V>> for &TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Add<Output = V> + Copy
+{
+    type Output = TimeSeries<T, V>;
+
+    fn add(self, rhs: &'b TimeSeries<T, V>) -> TimeSeries<T, V> {
+        self.add_aligned(rhs, Alignment::Inner)
+    }
+}
+
+/// Difference of two series at the times present in both
+impl<'b, T, V> Sub<&'b TimeSeries<T, V>> for &TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Sub<Output = V> + Copy
+{
+    type Output = TimeSeries<T, V>;
+
+    fn sub(self, rhs: &'b TimeSeries<T, V>) -> TimeSeries<T, V> {
+        self.sub_aligned(rhs, Alignment::Inner)
+    }
+}
+
+/// Product of two series at the times present in both
+impl<'b, T, V> Mul<&'b TimeSeries<T, V>> for &TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Mul<Output = V> + Copy
+{
+    type Output = TimeSeries<T, V>;
+
+    fn mul(self, rhs: &'b TimeSeries<T, V>) -> TimeSeries<T, V> {
+        self.mul_aligned(rhs, Alignment::Inner)
+    }
+}
+
+/// Quotient of two series at the times present in both
+impl<'b, T, V> Div<&'b TimeSeries<T, V>> for &TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Div<Output = V> + Copy
+{
+    type Output = TimeSeries<T, V>;
+
+    fn div(self, rhs: &'b TimeSeries<T, V>) -> TimeSeries<T, V> {
+        self.div_aligned(rhs, Alignment::Inner)
+    }
+}
+
+/// Test module for alignment module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Year;
+
+    fn series(points: &[(Year, f64)]) -> TimeSeries<Year> {
+        points.iter().map(|&(time, value)| TimeValue { time, value }).collect()
+    }
+
+    fn points(series: &TimeSeries<Year>) -> Vec<(Year, f64)> {
+        series.iter().map(|tv| (tv.time, tv.value)).collect()
+    }
+
+    fn income() -> TimeSeries<Year> {
+        series(&[(2017, 100.0), (2018, 110.0), (2020, 130.0)])
+    }
+
+    fn expense() -> TimeSeries<Year> {
+        series(&[(2016, 10.0), (2018, 50.0), (2019, 60.0)])
+    }
+
+    #[test]
+    fn inner() {
+        assert_eq!(points(&(&income() - &expense())), vec![(2018, 60.0)]);
+        assert_eq!(points(&(&income() + &expense())), vec![(2018, 160.0)]);
+        assert_eq!(points(&(&income() * &expense())), vec![(2018, 5500.0)]);
+        assert_eq!(points(&(&income() / &expense())), vec![(2018, 2.2)]);
+    }
+
+    #[test]
+    fn outer() {
+        assert_eq!(points(&income().sub_aligned(&expense(), Alignment::Outer(0.0))),
+                   vec![(2016, -10.0), (2017, 100.0), (2018, 60.0), (2019, -60.0), (2020, 130.0)]);
+        assert_eq!(points(&income().mul_aligned(&series(&[]), Alignment::Outer(1.0))),
+                   points(&income()));
+    }
+
+    #[test]
+    fn as_of() {
+        let rates = series(&[(2018, 0.5), (2020, 0.25)]);
+        assert_eq!(points(&income().mul_aligned(&rates, Alignment::AsOf)),
+                   vec![(2018, 55.0), (2020, 32.5)]);
+        assert_eq!(points(&expense().div_aligned(&rates, Alignment::AsOf)),
+                   vec![(2018, 100.0), (2019, 120.0)]);
+        assert_eq!(points(&income().add_aligned(&rates, Alignment::Inner)),
+                   vec![(2018, 110.5), (2020, 130.25)]);
+    }
+
+    /// Bid and ask quoted together
+    #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+    struct Quote(f64, f64);
+
+    impl Add for Quote {
+        type Output = Quote;
+
+        fn add(self, rhs: Quote) -> Quote {
+            Quote(self.0 + rhs.0, self.1 + rhs.1)
+        }
+    }
+
+    #[test]
+    fn non_scalar() {
+        let quotes = |points: &[(Year, Quote)]| -> TimeSeries<Year, Quote> {
+            points.iter().map(|&(time, value)| TimeValue { time, value }).collect()
+        };
+        let left = quotes(&[(2017, Quote(1.0, 2.0)), (2018, Quote(3.0, 4.0))]);
+        let right = quotes(&[(2018, Quote(0.5, 0.5))]);
+        assert_eq!(*(&left + &right), vec![TimeValue { time: 2018, value: Quote(3.5, 4.5) }]);
+        assert_eq!(*left.add_aligned(&right, Alignment::Outer(Quote(0.0, 0.0))),
+                   vec![TimeValue { time: 2017, value: Quote(1.0, 2.0) },
+                        TimeValue { time: 2018, value: Quote(3.5, 4.5) }]);
+    }
+}
@@ -0,0 +1,629 @@
+//! Business-day calendars and the conventions for rolling dates onto business days
+
+// --- module use statements ---
+
+use Date;
+use Debug;
+use Duration;
+use Time;
+use TimeSeries;
+use TimeValue;
+use Year;
+use YearMonth;
+use YearQuarter;
+use ZonedDateTime;
+use chrono::Datelike;
+use chrono::TimeZone;
+use chrono::Weekday;
+use zoned::on_local_date;
+
+// --- module enum definitions ---
+
+/// Convention for moving a date that is not a business day onto one
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Roll {
+    /// Leave the date where it is
+    Unadjusted,
+    /// Move to the next business day
+    Following,
+    /// Move to the next business day, unless that is in the next month,
+    /// in which case move to the previous business day
+    ModifiedFollowing,
+    /// Move to the previous business day
+    Preceding,
+    /// Move to the previous business day, unless that is in the previous month,
+    /// in which case move to the next business day
+    ModifiedPreceding,
+}
+
+/// A rule identifying a holiday in each year
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HolidayRule {
+    /// The same month and day every year
+    Fixed { month: u32, day: u32 },
+    /// The same month and day every year, observed on the Friday before when it falls on a
+    /// Saturday and on the Monday after when it falls on a Sunday
+    FixedObserved { month: u32, day: u32 },
+    /// The `nth` `weekday` of `month`, counting back from the end of the month when `nth` is
+    /// negative (-1 is the last)
+    NthWeekday { month: u32, weekday: Weekday, nth: i32 },
+    /// A number of days from (Western) Easter Sunday, for example -2 for Good Friday
+    EasterOffset(i64),
+    /// A single date, such as a one-off public holiday
+    OneOff(Date),
+}
+
+// --- module struct definitinos ---
+
+/// A calendar whose only non-business days are Saturdays and Sundays
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct WeekendsOnly;
+
+/// A calendar whose holidays are given by a set of `HolidayRule`s
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RuleCalendar {
+    /// Rules identifying the holidays of the calendar
+    pub rules: Vec<HolidayRule>,
+}
+
+/// A calendar combining others, where a holiday in any one is a holiday in the joint calendar
+#[derive(Debug, Default)]
+pub struct JointCalendar {
+    /// The calendars being combined
+    pub calendars: Vec<Box<dyn Calendar>>,
+}
+
+// --- module trait definitions ---
+
+/// Identifies business days and moves dates onto them
+pub trait Calendar: Debug {
+    /// Determines if `date` is a holiday
+    ///
+    ///  * `date` - Date to check
+    ///  * return - True if `date` is a holiday, regardless of weekends
+    ///
+    fn is_holiday(&self, date: &Date) -> bool;
+
+    /// Determines if `date` falls on a weekend
+    ///
+    ///  * `date` - Date to check
+    ///  * return - True if `date` is a Saturday or Sunday
+    ///
+    fn is_weekend(&self, date: &Date) -> bool {
+        // custom <fn calendar_is_weekend>
+        matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+        // end <fn calendar_is_weekend>
+    }
+
+    /// Determines if `date` is a business day
+    ///
+    ///  * `date` - Date to check
+    ///  * return - True if `date` is neither a weekend nor a holiday
+    ///
+    fn is_business_day(&self, date: &Date) -> bool {
+        // custom <fn calendar_is_business_day>
+        !self.is_weekend(date) && !self.is_holiday(date)
+        // end <fn calendar_is_business_day>
+    }
+
+    /// Moves `date` onto a business day according to `roll`
+    ///
+    ///  * `date` - Date to adjust
+    ///  * `roll` - Convention for choosing the business day
+    ///  * return - `date` if it is a business day, otherwise the business day chosen by `roll`
+    ///
+    fn adjust(&self, date: &Date, roll: Roll) -> Date {
+        // custom <fn calendar_adjust>
+
+        let following = |date: &Date| {
+            let mut result = *date;
+            while !self.is_business_day(&result) {
+                result = result.succ_opt().expect("date within chrono range");
+            }
+            result
+        };
+
+        let preceding = |date: &Date| {
+            let mut result = *date;
+            while !self.is_business_day(&result) {
+                result = result.pred_opt().expect("date within chrono range");
+            }
+            result
+        };
+
+        match roll {
+            Roll::Unadjusted => *date,
+            Roll::Following => following(date),
+            Roll::Preceding => preceding(date),
+            Roll::ModifiedFollowing => {
+                let result = following(date);
+                if result.month() == date.month() {
+                    result
+                } else {
+                    preceding(date)
+                }
+            }
+            Roll::ModifiedPreceding => {
+                let result = preceding(date);
+                if result.month() == date.month() {
+                    result
+                } else {
+                    following(date)
+                }
+            }
+        }
+
+        // end <fn calendar_adjust>
+    }
+
+    /// Moves `days` business days from `date`
+    ///
+    ///  * `date` - Date to start from
+    ///  * `days` - Number of business days to move, backwards if negative
+    ///  * return - The business day `days` business days from `date`
+    ///
+    fn add_business_days(&self, date: &Date, days: i64) -> Date {
+        // custom <fn calendar_add_business_days>
+
+        let step = if days < 0 { -1 } else { 1 };
+        let mut result = *date;
+        let mut remaining = days.abs();
+        while remaining > 0 {
+            result += Duration::days(step);
+            if self.is_business_day(&result) {
+                remaining -= 1;
+            }
+        }
+        result
+
+        // end <fn calendar_add_business_days>
+    }
+
+    /// Counts the business days from `from` until `to`
+    ///
+    ///  * `from` - Start of the period, included in the count
+    ///  * `to` - End of the period, excluded from the count
+    ///  * return - Business days in [`from`, `to`), negative if `to` is before `from`
+    ///
+    fn business_days_between(&self, from: &Date, to: &Date) -> i64 {
+        // custom <fn calendar_business_days_between>
+
+        if from > to {
+            return -self.business_days_between(to, from);
+        }
+
+        let mut business_days = 0;
+        let mut date = *from;
+        while date < *to {
+            if self.is_business_day(&date) {
+                business_days += 1;
+            }
+            date = date.succ_opt().expect("date within chrono range");
+        }
+        business_days
+
+        // end <fn calendar_business_days_between>
+    }
+
+    // custom <trait_calendar>
+    // end <trait_calendar>
+}
+
+/// A time that can be moved onto a business day of a `Calendar`
+pub trait Rollable: Sized {
+    /// Moves `self` onto a business day of `calendar` according to `roll`
+    ///
+    ///  * `calendar` - Calendar identifying business days
+    ///  * `roll` - Convention for choosing the business day
+    ///  * return - `self` moved onto a business day
+    ///
+    fn roll<C>(&self, calendar: &C, roll: Roll) -> Self where C: Calendar + ?Sized;
+
+    // custom <trait_rollable>
+    // end <trait_rollable>
+}
+
+// --- module impl definitions ---
+
+/// Provide `Calendar` implementation for `WeekendsOnly`
+impl Calendar for WeekendsOnly {
+    /// Determines if `date` is a holiday
+    ///
+    ///  * `date` - Date to check
+    ///  * return - Always false, as there are no holidays
+    ///
+    fn is_holiday(&self, _date: &Date) -> bool {
+        false
+    }
+
+    /// Counts the business days from `from` until `to`
+    ///
+    ///  * `from` - Start of the period, included in the count
+    ///  * `to` - End of the period, excluded from the count
+    ///  * return - Business days in [`from`, `to`), negative if `to` is before `from`
+    ///
+    fn business_days_between(&self, from: &Date, to: &Date) -> i64 {
+        // custom <fn calendar_weekends_only_business_days_between>
+
+        if from > to {
+            return -self.business_days_between(to, from);
+        }
+
+        // Every run of seven days holds five weekdays, leaving at most six to check
+        let days = to.signed_duration_since(*from).num_days();
+        let mut business_days = (days / 7) * 5;
+        let mut date = *from + Duration::days((days / 7) * 7);
+        while date < *to {
+            if !self.is_weekend(&date) {
+                business_days += 1;
+            }
+            date = date.succ_opt().expect("date within chrono range");
+        }
+        business_days
+
+        // end <fn calendar_weekends_only_business_days_between>
+    }
+}
+
+/// Provide `Calendar` implementation for `RuleCalendar`
+impl Calendar for RuleCalendar {
+    /// Determines if `date` is a holiday
+    ///
+    ///  * `date` - Date to check
+    ///  * return - True if any rule of the calendar falls on `date`
+    ///
+    fn is_holiday(&self, date: &Date) -> bool {
+        // custom <fn calendar_rule_calendar_is_holiday>
+        self.rules.iter().any(|rule| rule.falls_on(date))
+        // end <fn calendar_rule_calendar_is_holiday>
+    }
+}
+
+/// Provide `Calendar` implementation for `JointCalendar`
+impl Calendar for JointCalendar {
+    /// Determines if `date` is a holiday
+    ///
+    ///  * `date` - Date to check
+    ///  * return - True if `date` is a holiday in any of the calendars
+    ///
+    fn is_holiday(&self, date: &Date) -> bool {
+        // custom <fn calendar_joint_calendar_is_holiday>
+        self.calendars.iter().any(|calendar| calendar.is_holiday(date))
+        // end <fn calendar_joint_calendar_is_holiday>
+    }
+}
+
+impl HolidayRule {
+    /// The date the holiday is observed in `year`
+    ///
+    ///  * `year` - Year of the holiday
+    ///  * return - Date of the holiday, or `None` if it does not occur in `year`
+    ///
+    pub fn date_in(&self, year: i32) -> Option<Date> {
+        // custom <fn holiday_rule_date_in>
+
+        match *self {
+            HolidayRule::Fixed { month, day } => Date::from_ymd_opt(year, month, day),
+            HolidayRule::FixedObserved { month, day } => {
+                Date::from_ymd_opt(year, month, day).map(|date| match date.weekday() {
+                    Weekday::Sat => date - Duration::days(1),
+                    Weekday::Sun => date + Duration::days(1),
+                    _ => date,
+                })
+            }
+            HolidayRule::NthWeekday { month, weekday, nth } => {
+                if nth > 0 {
+                    Date::from_weekday_of_month_opt(year, month, weekday, nth as u8)
+                } else if nth < 0 {
+                    let last = YearMonth::new(year, month)?.last_day();
+                    let back = (7 + last.weekday().num_days_from_monday() -
+                                weekday.num_days_from_monday()) % 7;
+                    let date = last - Duration::days(i64::from(back) + 7 * i64::from(-nth - 1));
+                    if date.month() == month { Some(date) } else { None }
+                } else {
+                    None
+                }
+            }
+            HolidayRule::EasterOffset(days) => {
+                easter_sunday(year).map(|easter| easter + Duration::days(days))
+            }
+            HolidayRule::OneOff(date) => if date.year() == year { Some(date) } else { None },
+        }
+
+        // end <fn holiday_rule_date_in>
+    }
+
+    /// Determines if the holiday is observed on `date`
+    ///
+    ///  * `date` - Date to check
+    ///  * return - True if the holiday, in any year, is observed on `date`
+    ///
+    pub fn falls_on(&self, date: &Date) -> bool {
+        // custom <fn holiday_rule_falls_on>
+        // Observance can move a holiday across the year end, so check the neighbouring years
+        (date.year() - 1..date.year() + 2).any(|year| self.date_in(year) == Some(*date))
+        // end <fn holiday_rule_falls_on>
+    }
+}
+
+impl RuleCalendar {
+    /// Create a `RuleCalendar` with holidays given by `rules`
+    ///
+    ///  * `rules` - Rules identifying the holidays of the calendar
+    ///  * return - The calendar
+    ///
+    pub fn new(rules: Vec<HolidayRule>) -> RuleCalendar {
+        RuleCalendar { rules }
+    }
+}
+
+impl JointCalendar {
+    /// Create a `JointCalendar` combining `calendars`
+    ///
+    ///  * `calendars` - The calendars being combined
+    ///  * return - The joint calendar
+    ///
+    pub fn new(calendars: Vec<Box<dyn Calendar>>) -> JointCalendar {
+        JointCalendar { calendars }
+    }
+}
+
+/// Roll a `Date` onto a business day
+impl Rollable for Date {
+    fn roll<C>(&self, calendar: &C, roll: Roll) -> Self
+        where C: Calendar + ?Sized
+    {
+        calendar.adjust(self, roll)
+    }
+}
+
+/// Roll the local date of a `ZonedDateTime` onto a business day, keeping its time of day
+impl<Tz> Rollable for ZonedDateTime<Tz>
+    where Tz: TimeZone
+{
+    fn roll<C>(&self, calendar: &C, roll: Roll) -> Self
+        where C: Calendar + ?Sized
+    {
+        on_local_date(self, &calendar.adjust(&self.naive_local().date(), roll))
+    }
+}
+
+/// A `Year` is not a day, so rolling leaves it unchanged
+impl Rollable for Year {
+    fn roll<C>(&self, _calendar: &C, _roll: Roll) -> Self
+        where C: Calendar + ?Sized
+    {
+        *self
+    }
+}
+
+/// A `YearMonth` is not a day, so rolling leaves it unchanged
+impl Rollable for YearMonth {
+    fn roll<C>(&self, _calendar: &C, _roll: Roll) -> Self
+        where C: Calendar + ?Sized
+    {
+        *self
+    }
+}
+
+/// A `YearQuarter` is not a day, so rolling leaves it unchanged
+impl Rollable for YearQuarter {
+    fn roll<C>(&self, _calendar: &C, _roll: Roll) -> Self
+        where C: Calendar + ?Sized
+    {
+        *self
+    }
+}
+
+impl<T> TimeSeries<T>
+    where T: Time<T = T> + Rollable + Ord
+{
+    /// Moves the times of the series onto business days of `calendar`
+    ///
+    /// Values whose times roll onto the same time are summed. Rolling keeps any time of day,
+    /// so rolled times are sorted again.
+    ///
+    ///  * `calendar` - Calendar identifying business days
+    ///  * `roll` - Convention for choosing the business day
+    ///  * return - Series with each time moved onto a business day
+    ///
+    pub fn roll<C>(&self, calendar: &C, roll: Roll) -> TimeSeries<T>
+        where C: Calendar + ?Sized
+    {
+        // custom <fn time_series_roll>
+
+        let data = self.iter()
+            .map(|tv| {
+                     TimeValue {
+                         time: tv.time.roll(calendar, roll),
+                         value: tv.value,
+                     }
+                 })
+            .collect();
+        TimeSeries::new_summing(data)
+
+        // end <fn time_series_roll>
+    }
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Western Easter Sunday of `year`, by the anonymous Gregorian algorithm
+fn easter_sunday(year: i32) -> Option<Date> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    Date::from_ymd_opt(year, month as u32, day as u32)
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for calendar module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DateTime;
+    use TimeSearchable;
+    use chrono::Utc;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn us() -> RuleCalendar {
+        RuleCalendar::new(vec![HolidayRule::FixedObserved { month: 1, day: 1 },
+                               HolidayRule::NthWeekday {
+                                   month: 5,
+                                   weekday: Weekday::Mon,
+                                   nth: -1,
+                               },
+                               HolidayRule::FixedObserved { month: 7, day: 4 },
+                               HolidayRule::NthWeekday {
+                                   month: 11,
+                                   weekday: Weekday::Thu,
+                                   nth: 4,
+                               },
+                               HolidayRule::FixedObserved { month: 12, day: 25 }])
+    }
+
+    fn uk() -> RuleCalendar {
+        RuleCalendar::new(vec![HolidayRule::EasterOffset(-2),
+                               HolidayRule::EasterOffset(1),
+                               HolidayRule::Fixed { month: 12, day: 26 }])
+    }
+
+    #[test]
+    fn holiday_rules() {
+        assert_eq!(easter_sunday(2017), Some(date(2017, 4, 16)));
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(HolidayRule::NthWeekday {
+                           month: 5,
+                           weekday: Weekday::Mon,
+                           nth: -1,
+                       }
+                       .date_in(2017),
+                   Some(date(2017, 5, 29)));
+        assert_eq!(HolidayRule::NthWeekday {
+                           month: 11,
+                           weekday: Weekday::Thu,
+                           nth: 4,
+                       }
+                       .date_in(2017),
+                   Some(date(2017, 11, 23)));
+        // 2022-01-01 was a Saturday, observed on Friday 2021-12-31
+        assert!(HolidayRule::FixedObserved { month: 1, day: 1 }.falls_on(&date(2021, 12, 31)));
+        assert!(!HolidayRule::FixedObserved { month: 1, day: 1 }.falls_on(&date(2022, 1, 1)));
+    }
+
+    #[test]
+    fn is_business_day() {
+        let us = us();
+        assert!(!us.is_business_day(&date(2017, 7, 4)));
+        assert!(!us.is_business_day(&date(2017, 10, 14)));
+        assert!(us.is_business_day(&date(2017, 10, 16)));
+        assert!(!us.is_business_day(&date(2017, 12, 25)));
+    }
+
+    #[test]
+    fn joint_calendar() {
+        let joint = JointCalendar::new(vec![Box::new(us()), Box::new(uk())]);
+        assert!(!joint.is_business_day(&date(2017, 4, 14)));
+        assert!(!joint.is_business_day(&date(2017, 7, 4)));
+        assert!(!joint.is_business_day(&date(2017, 12, 26)));
+        assert!(joint.is_business_day(&date(2017, 12, 27)));
+    }
+
+    #[test]
+    fn adjust() {
+        let us = us();
+        // Saturday 2017-09-30 rolls into October when following
+        assert_eq!(us.adjust(&date(2017, 9, 30), Roll::Following), date(2017, 10, 2));
+        assert_eq!(us.adjust(&date(2017, 9, 30), Roll::ModifiedFollowing),
+                   date(2017, 9, 29));
+        assert_eq!(us.adjust(&date(2017, 9, 30), Roll::Preceding), date(2017, 9, 29));
+        // Sunday 2017-10-01 rolls into September when preceding
+        assert_eq!(us.adjust(&date(2017, 10, 1), Roll::ModifiedPreceding),
+                   date(2017, 10, 2));
+        assert_eq!(us.adjust(&date(2017, 7, 4), Roll::Following), date(2017, 7, 5));
+        assert_eq!(us.adjust(&date(2017, 7, 4), Roll::Unadjusted), date(2017, 7, 4));
+    }
+
+    #[test]
+    fn business_days() {
+        let us = us();
+        assert_eq!(us.business_days_between(&date(2017, 7, 3), &date(2017, 7, 10)), 4);
+        assert_eq!(us.business_days_between(&date(2017, 7, 10), &date(2017, 7, 3)), -4);
+        assert_eq!(WeekendsOnly.business_days_between(&date(2017, 7, 3), &date(2017, 7, 10)),
+                   5);
+        assert_eq!(us.add_business_days(&date(2017, 7, 3), 1), date(2017, 7, 5));
+        assert_eq!(us.add_business_days(&date(2017, 7, 5), -1), date(2017, 7, 3));
+    }
+
+    #[test]
+    fn roll_series() {
+        let series = TimeSeries::try_from_vec(vec![TimeValue {
+                                                       time: date(2017, 7, 3),
+                                                       value: 1.0,
+                                                   },
+                                                   TimeValue {
+                                                       time: date(2017, 7, 4),
+                                                       value: 2.0,
+                                                   },
+                                                   TimeValue {
+                                                       time: date(2017, 7, 5),
+                                                       value: 3.0,
+                                                   },
+                                                   TimeValue {
+                                                       time: date(2017, 7, 8),
+                                                       value: 4.0,
+                                                   }]).unwrap();
+        let rolled = series.roll(&us(), Roll::Following);
+        assert_eq!(rolled.into_vec(),
+                   vec![TimeValue {
+                            time: date(2017, 7, 3),
+                            value: 1.0,
+                        },
+                        TimeValue {
+                            time: date(2017, 7, 5),
+                            value: 5.0,
+                        },
+                        TimeValue {
+                            time: date(2017, 7, 10),
+                            value: 4.0,
+                        }]);
+
+        let noon = Utc.with_ymd_and_hms(2017, 7, 4, 12, 0, 0).unwrap();
+        assert_eq!(noon.roll(&us(), Roll::Preceding),
+                   Utc.with_ymd_and_hms(2017, 7, 3, 12, 0, 0).unwrap());
+
+        let at = |day, hour| Utc.with_ymd_and_hms(2017, 7, day, hour, 0, 0).unwrap();
+        let weekend: TimeSeries<DateTime> = [(at(8, 23), 1.0), (at(9, 1), 2.0), (at(10, 1), 4.0)]
+            .iter()
+            .map(|&(time, value)| TimeValue { time, value })
+            .collect();
+        let rolled = weekend.roll(&us(), Roll::Following);
+        assert_eq!(rolled.into_vec(),
+                   vec![TimeValue {
+                            time: at(10, 1),
+                            value: 6.0,
+                        },
+                        TimeValue {
+                            time: at(10, 23),
+                            value: 1.0,
+                        }]);
+        assert_eq!(weekend.roll(&us(), Roll::Following).on_or_before(at(10, 1)), 0..1);
+    }
+}
@@ -0,0 +1,331 @@
+//! Day-count conventions for measuring the years between two times
+
+// --- module use statements ---
+
+use Calendar;
+use Debug;
+use WeekendsOnly;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+
+// --- module struct definitinos ---
+
+/// *Actual/365 Fixed* - elapsed time over a fixed 365 day year
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Act365Fixed;
+
+/// *Actual/360* - elapsed time over a fixed 360 day year
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Act360;
+
+/// *Actual/Actual ISDA* - elapsed time in each calendar year over the length of that year
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ActActIsda;
+
+/// *30/360 US* - bond basis, including the end of February adjustments
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Thirty360Us;
+
+/// *30E/360* - eurobond basis
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Thirty360European;
+
+/// *Business/252* - business days of `calendar` over a 252 day year
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Business252<C = WeekendsOnly> {
+    /// Calendar identifying the business days counted
+    pub calendar: C,
+}
+
+// --- module trait definitions ---
+
+/// A convention for converting the interval between two times into years
+pub trait DayCount: Debug {
+    /// Years from `from` until `to` under the convention
+    ///
+    ///  * `from` - Start of the period
+    ///  * `to` - End of the period
+    ///  * return - Years from `from` until `to`, negative if `to` is before `from`
+    ///
+    fn year_fraction(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> f64;
+
+    // custom <trait_day_count>
+    // end <trait_day_count>
+}
+
+// --- module impl definitions ---
+
+/// Provide `DayCount` implementation for `Act365Fixed`
+impl DayCount for Act365Fixed {
+    /// Years from `from` until `to` under the convention
+    ///
+    ///  * `from` - Start of the period
+    ///  * `to` - End of the period
+    ///  * return - Years from `from` until `to`, negative if `to` is before `from`
+    ///
+    fn year_fraction(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> f64 {
+        // custom <fn day_count_act365_fixed_year_fraction>
+        days_between(from, to) / 365.0
+        // end <fn day_count_act365_fixed_year_fraction>
+    }
+}
+
+/// Provide `DayCount` implementation for `Act360`
+impl DayCount for Act360 {
+    /// Years from `from` until `to` under the convention
+    ///
+    ///  * `from` - Start of the period
+    ///  * `to` - End of the period
+    ///  * return - Years from `from` until `to`, negative if `to` is before `from`
+    ///
+    fn year_fraction(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> f64 {
+        // custom <fn day_count_act360_year_fraction>
+        days_between(from, to) / 360.0
+        // end <fn day_count_act360_year_fraction>
+    }
+}
+
+/// Provide `DayCount` implementation for `ActActIsda`
+impl DayCount for ActActIsda {
+    /// Years from `from` until `to` under the convention
+    ///
+    ///  * `from` - Start of the period
+    ///  * `to` - End of the period
+    ///  * return - Years from `from` until `to`, negative if `to` is before `from`
+    ///
+    fn year_fraction(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> f64 {
+        // custom <fn day_count_act_act_isda_year_fraction>
+
+        ordered(from, to, |from, to| {
+            let mut years = 0.0;
+            let mut start = *from;
+            while start < *to {
+                let year = start.year();
+                let next_year = start_of_year(year + 1);
+                let end = if next_year < *to { next_year } else { *to };
+                years += days_between(&start, &end) / days_in_year(year);
+                start = end;
+            }
+            years
+        })
+
+        // end <fn day_count_act_act_isda_year_fraction>
+    }
+}
+
+/// Provide `DayCount` implementation for `Thirty360Us`
+impl DayCount for Thirty360Us {
+    /// Years from `from` until `to` under the convention
+    ///
+    ///  * `from` - Start of the period
+    ///  * `to` - End of the period
+    ///  * return - Years from `from` until `to`, negative if `to` is before `from`
+    ///
+    fn year_fraction(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> f64 {
+        // custom <fn day_count_thirty360_us_year_fraction>
+
+        ordered(from, to, |from, to| {
+            let (d1, d2) = (from.date(), to.date());
+            let mut day1 = d1.day();
+            let mut day2 = d2.day();
+            let d1_end_of_february = is_end_of_february(&d1);
+            if d1_end_of_february && is_end_of_february(&d2) {
+                day2 = 30;
+            }
+            if d1_end_of_february {
+                day1 = 30;
+            }
+            if day2 == 31 && day1 >= 30 {
+                day2 = 30;
+            }
+            if day1 == 31 {
+                day1 = 30;
+            }
+            thirty_360(&d1, &d2, day1, day2)
+        })
+
+        // end <fn day_count_thirty360_us_year_fraction>
+    }
+}
+
+/// Provide `DayCount` implementation for `Thirty360European`
+impl DayCount for Thirty360European {
+    /// Years from `from` until `to` under the convention
+    ///
+    ///  * `from` - Start of the period
+    ///  * `to` - End of the period
+    ///  * return - Years from `from` until `to`, negative if `to` is before `from`
+    ///
+    fn year_fraction(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> f64 {
+        // custom <fn day_count_thirty360_european_year_fraction>
+
+        ordered(from, to, |from, to| {
+            let (d1, d2) = (from.date(), to.date());
+            thirty_360(&d1, &d2, d1.day().min(30), d2.day().min(30))
+        })
+
+        // end <fn day_count_thirty360_european_year_fraction>
+    }
+}
+
+/// Provide `DayCount` implementation for `Business252`
+impl<C> DayCount for Business252<C>
+    where C: Calendar
+{
+    /// Years from `from` until `to` under the convention
+    ///
+    ///  * `from` - Start of the period
+    ///  * `to` - End of the period
+    ///  * return - Years from `from` until `to`, negative if `to` is before `from`
+    ///
+    fn year_fraction(&self, from: &NaiveDateTime, to: &NaiveDateTime) -> f64 {
+        // custom <fn day_count_business252_year_fraction>
+        self.calendar.business_days_between(&from.date(), &to.date()) as f64 / 252.0
+        // end <fn day_count_business252_year_fraction>
+    }
+}
+
+impl<C> Business252<C>
+    where C: Calendar
+{
+    /// Create a `Business252` counting the business days of `calendar`
+    ///
+    ///  * `calendar` - Calendar identifying the business days counted
+    ///  * return - The day count
+    ///
+    pub fn new(calendar: C) -> Business252<C> {
+        Business252 { calendar }
+    }
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Elapsed days, including any fraction of a day, from `from` until `to`
+fn days_between(from: &NaiveDateTime, to: &NaiveDateTime) -> f64 {
+    to.signed_duration_since(*from).num_milliseconds() as f64 / 86_400_000.0
+}
+
+/// Applies `year_fraction` to the period in order, negating the result when `to` precedes `from`
+fn ordered<F>(from: &NaiveDateTime, to: &NaiveDateTime, year_fraction: F) -> f64
+    where F: Fn(&NaiveDateTime, &NaiveDateTime) -> f64
+{
+    if from > to {
+        -year_fraction(to, from)
+    } else {
+        year_fraction(from, to)
+    }
+}
+
+/// Midnight on January 1 of `year`
+fn start_of_year(year: i32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("year within chrono range")
+}
+
+/// Number of days in `year`
+fn days_in_year(year: i32) -> f64 {
+    if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+        366.0
+    } else {
+        365.0
+    }
+}
+
+/// True if `date` is the last day of February
+fn is_end_of_february(date: &NaiveDate) -> bool {
+    date.month() == 2 && date.succ_opt().is_some_and(|next| next.month() == 3)
+}
+
+/// The 30/360 year fraction given the already adjusted days of the month
+fn thirty_360(d1: &NaiveDate, d2: &NaiveDate, day1: u32, day2: u32) -> f64 {
+    let days = 360 * (d2.year() - d1.year()) + 30 * (d2.month() as i32 - d1.month() as i32) +
+               (day2 as i32 - day1 as i32);
+    days as f64 / 360.0
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for day_count module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HolidayRule;
+    use RuleCalendar;
+
+    fn dt(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn act365_fixed() {
+        assert_relative_eq!(Act365Fixed.year_fraction(&dt(2017, 1, 1), &dt(2018, 1, 1)), 1.0);
+        assert_relative_eq!(Act365Fixed.year_fraction(&dt(2020, 1, 1), &dt(2021, 1, 1)),
+                            366.0 / 365.0);
+        assert_relative_eq!(Act365Fixed.year_fraction(&dt(2018, 1, 1), &dt(2017, 1, 1)), -1.0);
+
+        let noon = NaiveDate::from_ymd_opt(2017, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        assert_relative_eq!(Act365Fixed.year_fraction(&dt(2017, 1, 1), &noon), 0.5 / 365.0);
+    }
+
+    #[test]
+    fn act360() {
+        assert_relative_eq!(Act360.year_fraction(&dt(2017, 1, 1), &dt(2017, 7, 1)),
+                            181.0 / 360.0);
+        assert_relative_eq!(Act360.year_fraction(&dt(2017, 7, 1), &dt(2017, 1, 1)),
+                            -181.0 / 360.0);
+    }
+
+    #[test]
+    fn act_act_isda() {
+        assert_relative_eq!(ActActIsda.year_fraction(&dt(2019, 1, 1), &dt(2021, 1, 1)), 2.0);
+        assert_relative_eq!(ActActIsda.year_fraction(&dt(2019, 11, 1), &dt(2020, 3, 1)),
+                            61.0 / 365.0 + 60.0 / 366.0);
+        assert_relative_eq!(ActActIsda.year_fraction(&dt(2020, 3, 1), &dt(2019, 11, 1)),
+                            -(61.0 / 365.0 + 60.0 / 366.0));
+    }
+
+    #[test]
+    fn thirty360_us() {
+        assert_relative_eq!(Thirty360Us.year_fraction(&dt(2017, 1, 31), &dt(2017, 3, 31)),
+                            60.0 / 360.0);
+        assert_relative_eq!(Thirty360Us.year_fraction(&dt(2017, 1, 15), &dt(2017, 3, 31)),
+                            76.0 / 360.0);
+        assert_relative_eq!(Thirty360Us.year_fraction(&dt(2017, 2, 28), &dt(2017, 3, 31)),
+                            30.0 / 360.0);
+        assert_relative_eq!(Thirty360Us.year_fraction(&dt(2016, 2, 29), &dt(2017, 2, 28)), 1.0);
+    }
+
+    #[test]
+    fn thirty360_european() {
+        assert_relative_eq!(Thirty360European.year_fraction(&dt(2017, 1, 15), &dt(2017, 3, 31)),
+                            75.0 / 360.0);
+        assert_relative_eq!(Thirty360European.year_fraction(&dt(2017, 2, 28), &dt(2017, 3, 31)),
+                            32.0 / 360.0);
+    }
+
+    #[test]
+    fn business252() {
+        // Monday 2017-10-02 through Monday 2017-10-16 spans ten weekdays
+        let business252 = Business252::new(WeekendsOnly);
+        assert_relative_eq!(business252.year_fraction(&dt(2017, 10, 2), &dt(2017, 10, 16)),
+                            10.0 / 252.0);
+        // Saturday through the following Tuesday spans Monday only
+        assert_relative_eq!(business252.year_fraction(&dt(2017, 10, 7), &dt(2017, 10, 10)),
+                            1.0 / 252.0);
+        assert_relative_eq!(business252.year_fraction(&dt(2017, 10, 10), &dt(2017, 10, 7)),
+                            -1.0 / 252.0);
+
+        // Columbus Day 2017-10-09 is not counted
+        let holidays = RuleCalendar::new(vec![HolidayRule::OneOff(NaiveDate::from_ymd_opt(2017,
+                                                                                         10,
+                                                                                         9)
+                                                                          .unwrap())]);
+        assert_relative_eq!(Business252::new(holidays)
+                                .year_fraction(&dt(2017, 10, 2), &dt(2017, 10, 16)),
+                            9.0 / 252.0);
+    }
+}
@@ -0,0 +1,299 @@
+//! Support for fractional years, giving annual models sub-annual precision
+
+// --- module use statements ---
+
+use Add;
+use Date;
+use DayCount;
+use Duration;
+use Tenor;
+use TenorShift;
+use TenorUnit;
+use Time;
+use Year;
+use chrono::Datelike;
+use std::fmt;
+use std::ops::Sub;
+use time_step::whole;
+
+// --- module constants ---
+
+/// Fixed-point units in a year, divisible by 12, 365 and 366 so that months and the
+/// days of every year are whole numbers of units
+const UNITS_PER_YEAR: i64 = 2_671_800_000;
+
+// --- module struct definitinos ---
+
+/// A year with a fractional part, such as 2031.5, held in fixed point so it is totally ordered
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct FractionalYear {
+    /// Fixed-point units since the start of year 0
+    units: i64,
+}
+
+// --- module impl definitions ---
+
+/// `FractionalYear` is its own time component
+impl Time for FractionalYear {
+    type T = Self;
+
+    /// Acess the time field of data
+    ///
+    ///  * return - The time associated with the data
+    ///
+    fn time(&self) -> Self::T {
+        // custom <fn time_fractional_year_time>
+        *self
+        // end <fn time_fractional_year_time>
+    }
+
+    /// Years from self until `time`
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until(&self, time: &Self::T) -> f64 {
+        // custom <fn time_fractional_year_years_until>
+        (time.units - self.units) as f64 / UNITS_PER_YEAR as f64
+        // end <fn time_fractional_year_years_until>
+    }
+
+    /// Years from self until `time` measured with `day_count`
+    ///
+    /// A `FractionalYear` is already measured in years, so `day_count` has no effect
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, _day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_fractional_year_years_until_with>
+        self.years_until(time)
+        // end <fn time_fractional_year_years_until_with>
+    }
+}
+
+impl FractionalYear {
+    /// Create a `FractionalYear` from a number of years
+    ///
+    ///  * `years` - Years since the start of year 0, such as 2031.5
+    ///  * return - The nearest `FractionalYear`
+    ///
+    pub fn from_years(years: f64) -> FractionalYear {
+        FractionalYear { units: (years * UNITS_PER_YEAR as f64).round() as i64 }
+    }
+
+    /// Moves `self` by a number of years without overflowing
+    ///
+    ///  * `years` - Years to move, backwards if negative
+    ///  * return - `self` moved by `years`, or `None` if `years` is not finite or the result
+    ///    is out of range
+    ///
+    pub(crate) fn checked_add_years(&self, years: f64) -> Option<FractionalYear> {
+        let units = self.units.checked_add(whole(years * UNITS_PER_YEAR as f64)?)?;
+        Some(FractionalYear { units })
+    }
+
+    /// The `FractionalYear` at the start of `date`
+    ///
+    ///  * `date` - Date to convert
+    ///  * return - The year of `date` plus the elapsed days of that year over its length
+    ///
+    pub fn from_date(date: &Date) -> FractionalYear {
+        // custom <fn fractional_year_from_date>
+        FractionalYear {
+            units: i64::from(date.year()) * UNITS_PER_YEAR +
+                   i64::from(date.ordinal0()) * units_per_day(date.year()),
+        }
+        // end <fn fractional_year_from_date>
+    }
+
+    /// Years since the start of year 0
+    pub fn years(&self) -> f64 {
+        self.units as f64 / UNITS_PER_YEAR as f64
+    }
+
+    /// The whole year containing `self`
+    pub fn floor_year(&self) -> Year {
+        self.units.div_euclid(UNITS_PER_YEAR) as Year
+    }
+
+    /// Converts to a `Year` without loss
+    ///
+    ///  * return - The `Year`, or `None` if `self` has a fractional part
+    ///
+    pub fn to_year(&self) -> Option<Year> {
+        if self.units % UNITS_PER_YEAR == 0 {
+            Some(self.floor_year())
+        } else {
+            None
+        }
+    }
+
+    /// The date containing `self`
+    ///
+    /// Converting a date to a `FractionalYear` and back returns the original date.
+    ///
+    ///  * return - The date whose day contains `self`, or `None` if its year is outside the
+    ///    years a `Date` can represent
+    ///
+    pub fn to_date(&self) -> Option<Date> {
+        // custom <fn fractional_year_to_date>
+        let year = self.floor_year();
+        let day = self.units.rem_euclid(UNITS_PER_YEAR) / units_per_day(year);
+        Date::from_yo_opt(year, day as u32 + 1)
+        // end <fn fractional_year_to_date>
+    }
+}
+
+impl From<Year> for FractionalYear {
+    fn from(year: Year) -> FractionalYear {
+        FractionalYear { units: i64::from(year) * UNITS_PER_YEAR }
+    }
+}
+
+impl From<Date> for FractionalYear {
+    fn from(date: Date) -> FractionalYear {
+        FractionalYear::from_date(&date)
+    }
+}
+
+/// Moves the time forward by a number of years, panicking if the result is out of range
+impl Add<f64> for FractionalYear {
+    type Output = FractionalYear;
+
+    fn add(self, rhs: f64) -> FractionalYear {
+        self.checked_add_years(rhs).expect("fractional year within range")
+    }
+}
+
+/// Years between two times
+impl Sub for FractionalYear {
+    type Output = f64;
+
+    fn sub(self, rhs: FractionalYear) -> f64 {
+        rhs.years_until(&self)
+    }
+}
+
+/// Move a `FractionalYear` by a `Tenor`
+///
+/// Month and year tenors are exact. Day and week tenors move the date containing the time,
+/// keeping the same fraction of the day.
+impl TenorShift for FractionalYear {
+    fn add_tenor(&self, tenor: &Tenor, _end_of_month: bool) -> Option<Self> {
+        // custom <fn tenor_shift_fractional_year_add_tenor>
+
+        match tenor.months() {
+            Some(months) => {
+                let units = self.units.checked_add(i64::from(months) * (UNITS_PER_YEAR / 12))?;
+                Some(FractionalYear { units })
+            }
+            None => {
+                let days = match tenor.unit {
+                    TenorUnit::Weeks => i64::from(tenor.count) * 7,
+                    _ => i64::from(tenor.count),
+                };
+                let date = self.to_date()?;
+                let within_day = self.units - FractionalYear::from_date(&date).units;
+                let moved = date.checked_add_signed(Duration::days(days))?;
+                Some(FractionalYear {
+                         units: FractionalYear::from_date(&moved).units +
+                                within_day * units_per_day(moved.year()) /
+                                units_per_day(date.year()),
+                     })
+            }
+        }
+
+        // end <fn tenor_shift_fractional_year_add_tenor>
+    }
+}
+
+impl fmt::Display for FractionalYear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.years())
+    }
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Fixed-point units in a day of `year`
+fn units_per_day(year: Year) -> i64 {
+    if Date::from_ymd_opt(year, 2, 29).is_some() {
+        UNITS_PER_YEAR / 366
+    } else {
+        UNITS_PER_YEAR / 365
+    }
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for fractional_year module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TimeSearchable;
+    use TimeSeries;
+    use TimeValue;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn years() {
+        let retirement = FractionalYear::from_years(2031.5);
+        assert_eq!(retirement.years(), 2031.5);
+        assert_eq!(retirement.floor_year(), 2031);
+        assert_eq!(retirement.to_year(), None);
+        assert_eq!(FractionalYear::from(2031).to_year(), Some(2031));
+        assert_relative_eq!(FractionalYear::from(2031).years_until(&retirement), 0.5);
+        assert_relative_eq!(retirement - FractionalYear::from(2032), -0.5);
+        assert_eq!(FractionalYear::from(2031) + 0.5, retirement);
+        assert_eq!(retirement.to_string(), "2031.5");
+    }
+
+    #[test]
+    fn dates() {
+        for &d in &[date(2017, 1, 1), date(2017, 10, 17), date(2016, 12, 31), date(-5, 3, 1)] {
+            assert_eq!(FractionalYear::from_date(&d).to_date(), Some(d));
+        }
+        assert_eq!(FractionalYear::from_date(&date(2017, 1, 1)),
+                   FractionalYear::from(2017));
+        assert_eq!(FractionalYear::from_years(2017.5).to_date(), Some(date(2017, 7, 2)));
+        assert_eq!(FractionalYear::from_years(2016.999999).to_date(), Some(date(2016, 12, 31)));
+        assert_eq!(FractionalYear::from(300_000).to_date(), None);
+    }
+
+    #[test]
+    fn add_tenor() {
+        let start = FractionalYear::from(2017);
+        assert_eq!(start.add_tenor(&"6M".parse().unwrap(), false),
+                   Some(FractionalYear::from_years(2017.5)));
+        assert_eq!(start.add_tenor(&"1W".parse().unwrap(), false),
+                   Some(FractionalYear::from_date(&date(2017, 1, 8))));
+        assert_eq!(FractionalYear::from(300_000).add_tenor(&"1D".parse().unwrap(), false),
+                   None);
+        assert_eq!(FractionalYear::from_years(1e10).add_tenor(&"12M".parse().unwrap(), false),
+                   None);
+        assert!(::std::panic::catch_unwind(|| FractionalYear::from_years(1e10) + 1.0).is_err());
+    }
+
+    #[test]
+    fn searchable() {
+        let series = TimeSeries::try_from_vec(vec![TimeValue {
+                                                       time: FractionalYear::from(2031),
+                                                       value: 1.0,
+                                                   },
+                                                   TimeValue {
+                                                       time: FractionalYear::from_years(2031.5),
+                                                       value: 2.0,
+                                                   }]).unwrap();
+        assert_eq!(series.after(FractionalYear::from_years(2031.25)), 1..2);
+        assert_eq!((&series + 1.0)[1].value, 3.0);
+    }
+}
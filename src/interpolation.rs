@@ -0,0 +1,296 @@
+//! Support for reading the value of a `TimeSeries` at any time, between or beyond its data
+
+// --- module use statements ---
+
+use Time;
+use TimeSearchable;
+use TimeSeries;
+use TimeSeriesView;
+use TimeValue;
+
+// --- module enum definitions ---
+
+/// How a value is read between two times of a series
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Interpolation {
+    /// The value at the latest time on or before
+    StepPrevious,
+    /// The value at the earliest time on or after
+    StepNext,
+    /// Straight line in years between the surrounding values
+    Linear,
+    /// Straight line in years between the logarithms of the surrounding values, which must be
+    /// positive
+    LogLinear,
+    /// Piecewise cubic in years that never overshoots the surrounding values
+    MonotoneCubic,
+}
+
+/// How a value is read before the first or after the last time of a series
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Extrapolation {
+    /// There is no value outside the data
+    Forbid,
+    /// The value at the nearest end of the data
+    Flat,
+    /// Linear, log-linear and cubic interpolation continue the line through the two nearest
+    /// points, while step interpolation stays flat
+    Extend,
+}
+
+// --- module impl definitions ---
+
+impl<T> TimeSeries<T>
+    where T: Time<T = T> + Ord
+{
+    /// Value of the series at `time`, with no value outside the data
+    ///
+    ///  * `time` - Time to read the value at
+    ///  * `interpolation` - How values between times of the series are read
+    ///  * return - The value, or `None` if the series is empty, `time` is outside the data or
+    ///    log-linear interpolation meets a value that is not positive
+    ///
+    pub fn value_at(&self, time: T, interpolation: Interpolation) -> Option<f64> {
+        self.view().value_at(time, interpolation)
+    }
+
+    /// Value of the series at `time`
+    ///
+    ///  * `time` - Time to read the value at
+    ///  * `interpolation` - How values between times of the series are read
+    ///  * `extrapolation` - How values outside the data are read
+    ///  * return - The value, or `None` if the series is empty, `extrapolation` forbids reading
+    ///    at `time` or log-linear interpolation meets a value that is not positive
+    ///
+    pub fn value_at_with(&self,
+                         time: T,
+                         interpolation: Interpolation,
+                         extrapolation: Extrapolation)
+                         -> Option<f64> {
+        self.view().value_at_with(time, interpolation, extrapolation)
+    }
+}
+
+impl<'a, T> TimeSeriesView<'a, T>
+    where T: Time<T = T> + Ord
+{
+    /// Value of the view at `time`, with no value outside the data
+    ///
+    ///  * `time` - Time to read the value at
+    ///  * `interpolation` - How values between times of the view are read
+    ///  * return - The value, or `None` if the view is empty, `time` is outside the data or
+    ///    log-linear interpolation meets a value that is not positive
+    ///
+    pub fn value_at(&self, time: T, interpolation: Interpolation) -> Option<f64> {
+        self.value_at_with(time, interpolation, Extrapolation::Forbid)
+    }
+
+    /// Value of the view at `time`
+    ///
+    ///  * `time` - Time to read the value at
+    ///  * `interpolation` - How values between times of the view are read
+    ///  * `extrapolation` - How values outside the data are read
+    ///  * return - The value, or `None` if the view is empty, `extrapolation` forbids reading
+    ///    at `time` or log-linear interpolation meets a value that is not positive
+    ///
+    pub fn value_at_with(&self,
+                         time: T,
+                         interpolation: Interpolation,
+                         extrapolation: Extrapolation)
+                         -> Option<f64> {
+        // custom <fn time_series_view_value_at_with>
+
+        let (first, last) = (self.first()?, self.last()?);
+        let previous = self.on_or_before(time).end;
+
+        if previous > 0 && self[previous - 1].time == time {
+            return Some(self[previous - 1].value);
+        }
+
+        if previous == 0 || previous == self.len() {
+            let (end, neighbour) = if previous == 0 {
+                (first, self.get(1))
+            } else {
+                (last, self.len().checked_sub(2).map(|i| &self[i]))
+            };
+            return match (extrapolation, interpolation, neighbour) {
+                (Extrapolation::Forbid, _, _) => None,
+                (Extrapolation::Extend, Interpolation::LogLinear, Some(neighbour)) => {
+                    log_linear(end, neighbour, time)
+                }
+                (Extrapolation::Extend, Interpolation::Linear, Some(neighbour)) |
+                (Extrapolation::Extend, Interpolation::MonotoneCubic, Some(neighbour)) => {
+                    Some(linear(end, neighbour, time))
+                }
+                _ => Some(end.value),
+            };
+        }
+
+        let (left, right) = (&self[previous - 1], &self[previous]);
+        match interpolation {
+            Interpolation::StepPrevious => Some(left.value),
+            Interpolation::StepNext => Some(right.value),
+            Interpolation::Linear => Some(linear(left, right, time)),
+            Interpolation::LogLinear => log_linear(left, right, time),
+            Interpolation::MonotoneCubic => Some(self.monotone_cubic(previous - 1, time)),
+        }
+
+        // end <fn time_series_view_value_at_with>
+    }
+
+    // custom <impl TimeSeriesView>
+
+    /// Fritsch-Carlson monotone cubic between the items at `i` and `i + 1`
+    fn monotone_cubic(&self, i: usize, time: T) -> f64 {
+        let (left, right) = (&self[i], &self[i + 1]);
+        let width = left.years_until(&right.time);
+        let tangent_left = self.tangent(i);
+        let tangent_right = self.tangent(i + 1);
+
+        let s = left.years_until(&time) / width;
+        let (s2, s3) = (s * s, s * s * s);
+        (2.0 * s3 - 3.0 * s2 + 1.0) * left.value + (s3 - 2.0 * s2 + s) * width * tangent_left +
+        (-2.0 * s3 + 3.0 * s2) * right.value + (s3 - s2) * width * tangent_right
+    }
+
+    /// Slope of the monotone cubic at the item at `i`
+    fn tangent(&self, i: usize) -> f64 {
+        let secant = |j: usize| {
+            (self[j + 1].value - self[j].value) / self[j].years_until(&self[j + 1].time)
+        };
+        if i == 0 {
+            return secant(0);
+        }
+        if i == self.len() - 1 {
+            return secant(i - 1);
+        }
+
+        let (before, after) = (secant(i - 1), secant(i));
+        if before * after <= 0.0 {
+            return 0.0;
+        }
+        // Weighted harmonic mean keeps the curve within the data on each side
+        let width_before = self[i - 1].years_until(&self[i].time);
+        let width_after = self[i].years_until(&self[i + 1].time);
+        let weight_before = 2.0 * width_after + width_before;
+        let weight_after = width_after + 2.0 * width_before;
+        (weight_before + weight_after) / (weight_before / before + weight_after / after)
+    }
+
+    // end <impl TimeSeriesView>
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Value at `time` on the line through `a` and `b`
+fn linear<T>(a: &TimeValue<T>, b: &TimeValue<T>, time: T) -> f64
+    where T: Time<T = T>
+{
+    a.value + (b.value - a.value) * a.time.years_until(&time) / a.time.years_until(&b.time)
+}
+
+/// Value at `time` on the log-linear curve through `a` and `b`
+fn log_linear<T>(a: &TimeValue<T>, b: &TimeValue<T>, time: T) -> Option<f64>
+    where T: Time<T = T>
+{
+    if a.value <= 0.0 || b.value <= 0.0 {
+        return None;
+    }
+    let weight = a.time.years_until(&time) / a.time.years_until(&b.time);
+    Some((a.value.ln() + (b.value.ln() - a.value.ln()) * weight).exp())
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for interpolation module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Date;
+    use DateSeries;
+    use Duration;
+    use Year;
+
+    fn series(points: &[(Year, f64)]) -> TimeSeries<Year> {
+        points.iter().map(|&(time, value)| TimeValue { time, value }).collect()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn dates() -> DateSeries {
+        vec![TimeValue {
+                 time: date(2017, 1, 1),
+                 value: 1.0,
+             },
+             TimeValue {
+                 time: date(2018, 1, 1),
+                 value: 2.0,
+             }]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn interpolate() {
+        let dates = dates();
+        let mid = date(2017, 7, 2);
+        let weight = 182.0 / 365.0;
+        assert_eq!(dates.value_at(mid, Interpolation::StepPrevious), Some(1.0));
+        assert_eq!(dates.value_at(mid, Interpolation::StepNext), Some(2.0));
+        assert_relative_eq!(dates.value_at(mid, Interpolation::Linear).unwrap(), 1.0 + weight);
+        assert_relative_eq!(dates.value_at(mid, Interpolation::LogLinear).unwrap(),
+                            2.0_f64.powf(weight));
+        assert_eq!(dates.value_at(date(2018, 1, 1), Interpolation::StepPrevious), Some(2.0));
+        assert_eq!(series(&[(1, 0.0), (2, 1.0)]).value_at(1, Interpolation::LogLinear),
+                   Some(0.0));
+        assert_eq!(series(&[(1, 0.0), (3, 1.0)]).value_at(2, Interpolation::LogLinear), None);
+    }
+
+    #[test]
+    fn monotone_cubic() {
+        let start = date(2017, 1, 1);
+        let day = |days| start + Duration::days(days);
+        let steps: DateSeries = [(0, 0.0), (10, 0.0), (20, 1.0), (30, 1.0), (50, 3.0)]
+            .iter()
+            .map(|&(days, value)| TimeValue { time: day(days), value })
+            .collect();
+        let mut previous = 0.0;
+        for days in 0..51 {
+            let value = steps.value_at(day(days), Interpolation::MonotoneCubic).unwrap();
+            assert!(value >= previous && value <= 3.0);
+            if days <= 10 || (21..=30).contains(&days) {
+                assert_relative_eq!(value, previous);
+            }
+            previous = value;
+        }
+
+        let dates = dates();
+        assert_relative_eq!(dates.value_at(date(2017, 7, 2), Interpolation::MonotoneCubic)
+                                .unwrap(),
+                            dates.value_at(date(2017, 7, 2), Interpolation::Linear).unwrap());
+    }
+
+    #[test]
+    fn extrapolate() {
+        let s = series(&[(2017, 1.0), (2018, 2.0), (2019, 4.0)]);
+        assert_eq!(s.value_at(2016, Interpolation::Linear), None);
+        assert_eq!(s.value_at_with(2016, Interpolation::Linear, Extrapolation::Flat),
+                   Some(1.0));
+        assert_eq!(s.value_at_with(2016, Interpolation::Linear, Extrapolation::Extend),
+                   Some(0.0));
+        assert_eq!(s.value_at_with(2021, Interpolation::MonotoneCubic, Extrapolation::Extend),
+                   Some(8.0));
+        assert_relative_eq!(s.value_at_with(2020, Interpolation::LogLinear, Extrapolation::Extend)
+                                .unwrap(),
+                            8.0);
+        assert_eq!(s.value_at_with(2020, Interpolation::StepNext, Extrapolation::Extend),
+                   Some(4.0));
+        assert_eq!(TimeSeries::<Year>::default()
+                       .value_at_with(2017, Interpolation::Linear, Extrapolation::Flat),
+                   None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Support for lining up several `TimeSeries` on common times, such as rates against cash flows
+
+// --- module use statements ---
+
+use Tenor;
+use TimeSeries;
+use TimeStep;
+use TimeValue;
+
+// --- module enum definitions ---
+
+/// Which way an as-of join looks for a value
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    /// The latest value on or before the time
+    Backward,
+    /// The earliest value on or after the time
+    Forward,
+}
+
+/// How the times of joined series are chosen and matched
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Join {
+    /// Times present in every series
+    Inner,
+    /// Times present in any series
+    Outer,
+    /// Times of the first series, matching the others exactly
+    Left,
+    /// Times of the first series, matching the others by `direction` within `tolerance`
+    AsOf {
+        /// Which way to look for a value
+        direction: Direction,
+        /// Furthest a matched time may be from the time, if limited
+        tolerance: Option<Tenor>,
+    },
+}
+
+// --- module struct definitinos ---
+
+/// Series lined up on common times
+#[derive(Debug, PartialEq, Clone)]
+pub struct Joined<T> {
+    /// Times of the join in increasing order
+    pub times: Vec<T>,
+    /// One column per joined series, each holding a value or `None` for each time
+    pub columns: Vec<Vec<Option<f64>>>,
+}
+
+// --- module impl definitions ---
+
+impl<T> Joined<T>
+    where T: TimeStep + Ord
+{
+    /// Number of times in the join
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// True if the join has no times
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// The values of column `column` as a series, leaving out times without a value
+    ///
+    ///  * `column` - Position of the series in the join
+    ///  * return - The column as a series
+    ///
+    pub fn series(&self, column: usize) -> TimeSeries<T> {
+        TimeSeries::from_sorted(self.times
+                                    .iter()
+                                    .zip(&self.columns[column])
+                                    .filter_map(|(&time, value)| {
+                                                    value.map(|value| TimeValue { time, value })
+                                                })
+                                    .collect())
+    }
+}
+
+// --- module function definitions ---
+
+/// Lines up `series` on common times
+///
+///  * `series` - Series to join, the first deciding the times of left and as-of joins
+///  * `how` - How times are chosen and matched
+///  * return - The times of the join with one column per series, or `None` if the tolerance
+///    of an as-of join cannot move the times
+///
+pub fn join<T>(series: &[&TimeSeries<T>], how: Join) -> Option<Joined<T>>
+    where T: TimeStep + Ord
+{
+    // custom <fn join>
+
+    let times: Vec<T> = match (how, series.first()) {
+        (_, None) => Vec::new(),
+        (Join::Left, Some(first)) |
+        (Join::AsOf { .. }, Some(first)) => first.iter().map(|tv| tv.time).collect(),
+        (Join::Inner, Some(first)) => {
+            first.iter()
+                .map(|tv| tv.time)
+                .filter(|time| series[1..].iter().all(|s| exact(s, time).is_some()))
+                .collect()
+        }
+        (Join::Outer, Some(_)) => {
+            let mut times: Vec<T> =
+                series.iter().flat_map(|s| s.iter().map(|tv| tv.time)).collect();
+            times.sort();
+            times.dedup();
+            times
+        }
+    };
+
+    let columns = series.iter()
+        .enumerate()
+        .map(|(column, s)| {
+            times.iter()
+                .map(|time| match how {
+                         Join::AsOf { direction, tolerance } if column > 0 => {
+                             as_of(s, time, direction, tolerance)
+                         }
+                         _ => Some(exact(s, time)),
+                     })
+                .collect()
+        })
+        .collect::<Option<_>>()?;
+
+    Some(Joined { times, columns })
+
+    // end <fn join>
+}
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Value of `series` at exactly `time`
+fn exact<T>(series: &TimeSeries<T>, time: &T) -> Option<f64>
+    where T: TimeStep + Ord
+{
+    series.latest_on_or_before(*time).filter(|found| found.time == *time).map(|found| found.value)
+}
+
+/// Value of `series` nearest `time` in `direction`, within `tolerance`
+///
+///  * return - The value, if any, or `None` if `tolerance` cannot move `time`
+///
+fn as_of<T>(series: &TimeSeries<T>,
+            time: &T,
+            direction: Direction,
+            tolerance: Option<Tenor>)
+            -> Option<Option<f64>>
+    where T: TimeStep + Ord
+{
+    let found = match direction {
+        Direction::Backward => series.latest_on_or_before(*time),
+        Direction::Forward => series.earliest_on_or_after(*time),
+    };
+    let within = match (tolerance, direction) {
+        (None, _) => None,
+        (Some(tolerance), Direction::Backward) => Some(time.sub_period(&tolerance)?),
+        (Some(tolerance), Direction::Forward) => Some(time.add_period(&tolerance)?),
+    };
+    Some(found.filter(|found| match (within, direction) {
+                          (None, _) => true,
+                          (Some(limit), Direction::Backward) => found.time >= limit,
+                          (Some(limit), Direction::Forward) => found.time <= limit,
+                      })
+             .map(|found| found.value))
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for join module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Date;
+    use Year;
+
+    fn date(month: u32, day: u32) -> Date {
+        Date::from_ymd_opt(2017, month, day).unwrap()
+    }
+
+    fn series(points: &[(Date, f64)]) -> TimeSeries<Date> {
+        points.iter().map(|&(time, value)| TimeValue { time, value }).collect()
+    }
+
+    fn flows() -> TimeSeries<Date> {
+        series(&[(date(1, 15), 100.0), (date(2, 15), 200.0), (date(3, 15), 300.0)])
+    }
+
+    fn rates() -> TimeSeries<Date> {
+        series(&[(date(1, 1), 0.01), (date(2, 15), 0.02), (date(3, 20), 0.03)])
+    }
+
+    #[test]
+    fn exact_joins() {
+        let (flows, rates) = (flows(), rates());
+        let inner = join(&[&flows, &rates], Join::Inner).unwrap();
+        assert_eq!(inner.times, vec![date(2, 15)]);
+        assert_eq!(inner.columns, vec![vec![Some(200.0)], vec![Some(0.02)]]);
+
+        let outer = join(&[&flows, &rates], Join::Outer).unwrap();
+        assert_eq!(outer.len(), 5);
+        assert_eq!(outer.columns[0],
+                   vec![None, Some(100.0), Some(200.0), Some(300.0), None]);
+        assert_eq!(outer.series(1).into_vec(), rates.clone().into_vec());
+
+        let left = join(&[&flows, &rates, &flows], Join::Left).unwrap();
+        assert_eq!(left.times, vec![date(1, 15), date(2, 15), date(3, 15)]);
+        assert_eq!(left.columns[1], vec![None, Some(0.02), None]);
+        assert_eq!(left.columns[2], left.columns[0]);
+        assert!(join::<Date>(&[], Join::Outer).unwrap().is_empty());
+    }
+
+    #[test]
+    fn as_of_joins() {
+        let (flows, rates) = (flows(), rates());
+        let backward = join(&[&flows, &rates],
+                            Join::AsOf {
+                                direction: Direction::Backward,
+                                tolerance: None,
+                            })
+            .unwrap();
+        assert_eq!(backward.columns[1], vec![Some(0.01), Some(0.02), Some(0.02)]);
+
+        let forward = join(&[&flows, &rates],
+                           Join::AsOf {
+                               direction: Direction::Forward,
+                               tolerance: Some("1W".parse().unwrap()),
+                           })
+            .unwrap();
+        assert_eq!(forward.columns[1], vec![None, Some(0.02), Some(0.03)]);
+
+        let recent = join(&[&flows, &rates],
+                          Join::AsOf {
+                              direction: Direction::Backward,
+                              tolerance: Some("14D".parse().unwrap()),
+                          })
+            .unwrap();
+        assert_eq!(recent.columns[1], vec![Some(0.01), Some(0.02), None]);
+
+        let yearly = |points: &[(Year, f64)]| -> TimeSeries<Year> {
+            points.iter().map(|&(time, value)| TimeValue { time, value }).collect()
+        };
+        let (left, right) = (yearly(&[(2017, 1.0)]), yearly(&[(2017, 2.0)]));
+        let half_year = Join::AsOf {
+            direction: Direction::Backward,
+            tolerance: Some("6M".parse().unwrap()),
+        };
+        assert_eq!(join(&[&left, &right], half_year), None);
+    }
+}
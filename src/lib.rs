@@ -6,36 +6,132 @@ extern crate chrono;
 #[cfg(test)]
 #[macro_use]
 extern crate approx;
+#[cfg(test)]
+extern crate chrono_tz;
+#[macro_use]
+extern crate ndarray;
 extern crate num;
 
 // --- module pub use statements ---
 
+pub use alignment::Alignment;
+pub use calendar::Calendar;
+pub use calendar::HolidayRule;
+pub use calendar::JointCalendar;
+pub use calendar::Roll;
+pub use calendar::Rollable;
+pub use calendar::RuleCalendar;
+pub use calendar::WeekendsOnly;
+pub use day_count::Act360;
+pub use day_count::Act365Fixed;
+pub use day_count::ActActIsda;
+pub use day_count::Business252;
+pub use day_count::DayCount;
+pub use day_count::Thirty360European;
+pub use day_count::Thirty360Us;
+pub use fractional_year::FractionalYear;
+pub use interpolation::Extrapolation;
+pub use interpolation::Interpolation;
+pub use join::Direction;
+pub use join::Join;
+pub use join::Joined;
+pub use join::join;
+pub use missing::Fill;
+pub use missing::GapReport;
+pub use money::Currency;
+pub use money::Money;
+pub use money::MoneyError;
+pub use money::Rounding;
+pub use rate_curve::RateCurve;
+pub use resample::Aggregation;
+pub use resample::Bucketing;
+pub use resample::CalendarTime;
+pub use resample::Daily;
+pub use resample::FiscalYearly;
+pub use resample::Monthly;
+pub use resample::OhlcValue;
+pub use resample::Quarterly;
+pub use resample::Upsample;
+pub use resample::Yearly;
+pub use rolling::RollingStat;
+pub use rolling::Window;
+pub use schedule::Schedule;
+pub use schedule::Stub;
 pub use self::num::traits::NumOps;
+pub use statistics::Drawdown;
 pub use std::convert::Into;
 pub use std::fmt::Debug;
 pub use std::ops::Add;
 pub use std::ops::Deref;
 pub use std::ops::Range;
+pub use tenor::ParseTenorError;
+pub use tenor::Tenor;
+pub use tenor::TenorShift;
+pub use tenor::TenorUnit;
 pub use time_comparable::TimeComparable;
+pub use time_frame::TimeFrame;
+pub use time_frame::TimeFrameError;
+pub use time_interval::IntervalBound;
+pub use time_interval::TimeInterval;
 pub use time_revalue::TimeRevalue;
 pub use time_searchable::TimeSearchable;
+pub use time_series::DateSeries;
 pub use time_series::DateTimeSeries;
+pub use time_series::DuplicatePolicy;
+pub use time_series::FractionalYearTimeSeries;
+pub use time_series::MonthTimeSeries;
+pub use time_series::QuarterTimeSeries;
 pub use time_series::TimeSeries;
+pub use time_series::TimeSeriesError;
 pub use time_series::YearTimeSeries;
+pub use time_series::ZonedDateTimeSeries;
+pub use time_series_view::TimeSeriesView;
+pub use time_step::TimeStep;
 pub use time_value::Time;
 pub use time_value::TimeValue;
+pub use transform::Edge;
+pub use year_month::YearMonth;
+pub use year_quarter::YearQuarter;
 
-mod time_value;
-mod time_series;
+mod alignment;
+mod calendar;
+mod day_count;
+mod fractional_year;
+mod interpolation;
+mod join;
+mod missing;
+mod money;
+mod rate_curve;
+mod resample;
+mod rolling;
+mod schedule;
+mod statistics;
+mod tenor;
 mod time_comparable;
-mod time_searchable;
+mod time_frame;
+mod time_interval;
 mod time_revalue;
-mod rate_curve;
+mod time_searchable;
+mod time_series;
+mod time_series_view;
+mod time_step;
+mod time_value;
+mod transform;
+mod year_month;
+mod year_quarter;
+mod zoned;
 
 // --- module type aliases ---
 
 pub type Year = i32;
+pub type Date = chrono::NaiveDate;
 pub type DateTime = chrono::DateTime<chrono::Utc>;
+pub type ZonedDateTime<Tz> = chrono::DateTime<Tz>;
 pub type Duration = chrono::Duration;
+pub type DateValue = TimeValue<Date>;
 pub type DateTimeValue = TimeValue<DateTime>;
+pub type ZonedDateTimeValue<Tz> = TimeValue<ZonedDateTime<Tz>>;
 pub type YearValue = TimeValue<Year>;
+pub type FractionalYearValue = TimeValue<FractionalYear>;
+pub type YearMonthValue = TimeValue<YearMonth>;
+pub type YearQuarterValue = TimeValue<YearQuarter>;
@@ -0,0 +1,336 @@
+//! Support for missing values in a `TimeSeries`, finding the holes in input data and filling
+//! them before they reach a projection
+
+// --- module use statements ---
+
+use Interpolation;
+use Tenor;
+use Time;
+use TimeInterval;
+use TimeSeries;
+use TimeStep;
+use TimeValue;
+
+// --- module enum definitions ---
+
+/// How a missing value is filled
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Fill {
+    /// The latest value before it
+    Forward,
+    /// The earliest value after it
+    Backward,
+    /// The given value
+    Constant(f64),
+    /// The values around it, read with the given interpolation
+    Interpolate(Interpolation),
+}
+
+// --- module struct definitinos ---
+
+/// The times a series is missing when checked against an expected frequency
+#[derive(Debug, PartialEq, Clone)]
+pub struct GapReport<T> {
+    /// Number of times expected at the frequency
+    pub expected: usize,
+    /// Number of expected times that are absent or have a `NaN` value
+    pub missing: usize,
+    /// Each run of consecutive missing times, from its first to its last missing time
+    pub gaps: Vec<TimeInterval<T>>,
+}
+
+// --- module impl definitions ---
+
+impl<T> TimeSeries<T>
+    where T: Time<T = T> + Ord
+{
+    /// The series without its missing values, those that are `NaN`
+    pub fn drop_missing(&self) -> TimeSeries<T> {
+        TimeSeries::from_sorted(self.iter().filter(|tv| !tv.value.is_nan()).cloned().collect())
+    }
+
+    /// The series with missing values as `None`
+    pub fn to_options(&self) -> TimeSeries<T, Option<f64>> {
+        TimeSeries::from_sorted(self.iter()
+                                    .map(|tv| {
+                                             TimeValue {
+                                                 time: tv.time,
+                                                 value: Some(tv.value).filter(|v| !v.is_nan()),
+                                             }
+                                         })
+                                    .collect())
+    }
+
+    /// Fills missing values, those that are `NaN`
+    ///
+    /// A run of missing values with no value on the side `fill` needs, such as a leading run
+    /// filled forward, stays missing.
+    ///
+    ///  * `fill` - How each missing value is filled
+    ///  * `max_gap` - Longest run of consecutive missing values to fill, if limited. Longer
+    ///    runs stay missing.
+    ///  * return - The series with filled values
+    ///
+    pub fn fill_missing(&self, fill: Fill, max_gap: Option<usize>) -> TimeSeries<T> {
+        // custom <fn time_series_fill_missing>
+
+        let present = match fill {
+            Fill::Interpolate(_) => self.drop_missing(),
+            _ => TimeSeries::default(),
+        };
+        let mut data = self.to_vec();
+        let mut start = 0;
+        while start < data.len() {
+            if !data[start].value.is_nan() {
+                start += 1;
+                continue;
+            }
+            let end = (start..data.len()).find(|&i| !data[i].value.is_nan()).unwrap_or(data.len());
+            if max_gap.is_none_or(|max_gap| end - start <= max_gap) {
+                let before = start.checked_sub(1).map(|i| data[i].value);
+                let after = data.get(end).map(|tv| tv.value);
+                for tv in &mut data[start..end] {
+                    let value = match fill {
+                        Fill::Forward => before,
+                        Fill::Backward => after,
+                        Fill::Constant(value) => Some(value),
+                        Fill::Interpolate(interpolation) => {
+                            present.value_at(tv.time, interpolation)
+                        }
+                    };
+                    tv.value = value.unwrap_or(f64::NAN);
+                }
+            }
+            start = end;
+        }
+        TimeSeries::from_sorted(data)
+
+        // end <fn time_series_fill_missing>
+    }
+}
+
+impl<T> TimeSeries<T, Option<f64>>
+    where T: Time<T = T> + Ord
+{
+    /// The series with `None` values as `NaN`
+    pub fn to_nans(&self) -> TimeSeries<T> {
+        TimeSeries::from_sorted(self.iter()
+                                    .map(|tv| {
+                                             TimeValue {
+                                                 time: tv.time,
+                                                 value: tv.value.unwrap_or(f64::NAN),
+                                             }
+                                         })
+                                    .collect())
+    }
+}
+
+impl<T> TimeSeries<T>
+    where T: TimeStep + Ord
+{
+    /// Checks the series for times missing at `frequency` between its first and last times
+    ///
+    ///  * `frequency` - Expected spacing of the times, such as `1M`
+    ///  * return - Report of the missing times, or `None` if `frequency` does not move the
+    ///    times forward
+    ///
+    pub fn gaps(&self, frequency: &Tenor) -> Option<GapReport<T>> {
+        // custom <fn time_series_gaps>
+        match (self.first(), self.last()) {
+            (Some(first), Some(last)) => {
+                self.gaps_in(&TimeInterval::closed(first.time, last.time), frequency)
+            }
+            _ => {
+                Some(GapReport {
+                         expected: 0,
+                         missing: 0,
+                         gaps: Vec::new(),
+                     })
+            }
+        }
+        // end <fn time_series_gaps>
+    }
+
+    /// Checks the series for times missing at `frequency` over `expected`
+    ///
+    ///  * `expected` - Interval the series should cover, its times counted in steps of
+    ///    `frequency` from its start
+    ///  * `frequency` - Expected spacing of the times, such as `1M`
+    ///  * return - Report of the missing times, or `None` if `frequency` does not move the
+    ///    times forward
+    ///
+    pub fn gaps_in(&self,
+                   expected: &TimeInterval<T>,
+                   frequency: &Tenor)
+                   -> Option<GapReport<T>> {
+        // custom <fn time_series_gaps_in>
+
+        let grid = grid(expected, frequency)?;
+        let mut report = GapReport {
+            expected: grid.len(),
+            missing: 0,
+            gaps: Vec::new(),
+        };
+        let mut run: Option<(T, T)> = None;
+        let mut i = 0;
+        for time in grid {
+            while i < self.len() && self[i].time < time {
+                i += 1;
+            }
+            let present = self.get(i).is_some_and(|tv| tv.time == time && !tv.value.is_nan());
+            if present {
+                if let Some((first, last)) = run.take() {
+                    report.gaps.push(TimeInterval::closed(first, last));
+                }
+            } else {
+                report.missing += 1;
+                run = Some((run.map_or(time, |(first, _)| first), time));
+            }
+        }
+        if let Some((first, last)) = run {
+            report.gaps.push(TimeInterval::closed(first, last));
+        }
+        Some(report)
+
+        // end <fn time_series_gaps_in>
+    }
+
+    /// Adds the times missing at `frequency` between the first and last times, with `NaN`
+    /// values, ready for `fill_missing`
+    ///
+    ///  * `frequency` - Expected spacing of the times, such as `1M`
+    ///  * return - The series with every expected time, keeping any times off the frequency,
+    ///    or `None` if `frequency` does not move the times forward
+    ///
+    pub fn complete(&self, frequency: &Tenor) -> Option<TimeSeries<T>> {
+        // custom <fn time_series_complete>
+
+        let grid = match (self.first(), self.last()) {
+            (Some(first), Some(last)) => {
+                grid(&TimeInterval::closed(first.time, last.time), frequency)?
+            }
+            _ => return Some(TimeSeries::default()),
+        };
+        let mut data = Vec::with_capacity(grid.len().max(self.len()));
+        let mut observed = self.iter().peekable();
+        for time in grid {
+            while let Some(tv) = observed.next_if(|tv| tv.time < time) {
+                data.push(*tv);
+            }
+            if observed.peek().is_none_or(|tv| tv.time != time) {
+                data.push(TimeValue { time, value: f64::NAN });
+            }
+        }
+        data.extend(observed);
+        Some(TimeSeries::from_sorted(data))
+
+        // end <fn time_series_complete>
+    }
+}
+
+impl<T> GapReport<T> {
+    /// True if no expected time is missing
+    pub fn is_complete(&self) -> bool {
+        self.missing == 0
+    }
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Times of `interval` in steps of `frequency` from its start, or `None` if `frequency` does
+/// not move the times forward
+fn grid<T>(interval: &TimeInterval<T>, frequency: &Tenor) -> Option<Vec<T>>
+    where T: TimeStep + Ord
+{
+    if interval.start.add_period(frequency)? <= interval.start {
+        return None;
+    }
+    let mut times = Vec::new();
+    for step in 0.. {
+        let time = interval.start.add_period(&frequency.times(step)?)?;
+        if time > interval.end {
+            break;
+        }
+        if interval.contains(&time) {
+            times.push(time);
+        }
+    }
+    Some(times)
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for missing module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use YearMonth;
+
+    fn month(m: u32) -> YearMonth {
+        YearMonth::new(2017, m).unwrap()
+    }
+
+    fn monthly(points: &[(u32, f64)]) -> TimeSeries<YearMonth> {
+        points.iter().map(|&(m, value)| TimeValue { time: month(m), value }).collect()
+    }
+
+    fn values(series: &TimeSeries<YearMonth>) -> Vec<f64> {
+        series.iter().map(|tv| tv.value).collect()
+    }
+
+    fn with_holes() -> TimeSeries<YearMonth> {
+        monthly(&[(1, 1.0), (2, f64::NAN), (3, 3.0), (6, 6.0), (7, f64::NAN)])
+    }
+
+    #[test]
+    fn gaps() {
+        let monthly_step: Tenor = "1M".parse().unwrap();
+        let report = with_holes().gaps(&monthly_step).unwrap();
+        assert_eq!(report.expected, 7);
+        assert_eq!(report.missing, 4);
+        assert_eq!(report.gaps,
+                   vec![TimeInterval::closed(month(2), month(2)),
+                        TimeInterval::closed(month(4), month(5)),
+                        TimeInterval::closed(month(7), month(7))]);
+        assert!(!report.is_complete());
+
+        let year = TimeInterval::closed_open(month(1), YearMonth::new(2018, 1).unwrap());
+        let quarterly = with_holes().gaps_in(&year, &"3M".parse().unwrap()).unwrap();
+        assert_eq!((quarterly.expected, quarterly.missing), (4, 3));
+        assert_eq!(quarterly.gaps, vec![TimeInterval::closed(month(4), month(10))]);
+        assert!(with_holes().gaps(&"0M".parse().unwrap()).is_none());
+        assert!(TimeSeries::<YearMonth>::default().gaps(&monthly_step).unwrap().is_complete());
+    }
+
+    #[test]
+    fn fills() {
+        let full = with_holes().complete(&"1M".parse().unwrap()).unwrap();
+        assert_eq!(full.len(), 7);
+        assert!(full[3].value.is_nan() && full[4].value.is_nan());
+
+        let forward = values(&full.fill_missing(Fill::Forward, None));
+        assert_eq!(forward, vec![1.0, 1.0, 3.0, 3.0, 3.0, 6.0, 6.0]);
+        let backward = full.fill_missing(Fill::Backward, Some(1));
+        assert_eq!(values(&backward)[..3], [1.0, 3.0, 3.0]);
+        assert!(backward[3].value.is_nan() && backward[6].value.is_nan());
+        assert_eq!(values(&full.fill_missing(Fill::Constant(0.0), Some(2))),
+                   vec![1.0, 0.0, 3.0, 0.0, 0.0, 6.0, 0.0]);
+
+        let interpolated = full.fill_missing(Fill::Interpolate(Interpolation::Linear), None);
+        assert_relative_eq!(interpolated[1].value, 2.0);
+        assert_relative_eq!(interpolated[4].value, 5.0);
+        assert!(interpolated[6].value.is_nan());
+    }
+
+    #[test]
+    fn options() {
+        let options = with_holes().to_options();
+        assert_eq!(options[1].value, None);
+        assert_eq!(options[2].value, Some(3.0));
+        let restored = options.to_nans();
+        assert!(restored[1].value.is_nan());
+        assert_eq!(values(&restored.drop_missing()), vec![1.0, 3.0, 6.0]);
+    }
+}
@@ -0,0 +1,551 @@
+//! Support for currency amounts held as decimals, so forecast outputs reconcile to the cent
+
+// --- module use statements ---
+
+use Add;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+
+// --- module enum definitions ---
+
+/// How an amount halfway between two representable amounts is rounded
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Rounding {
+    /// Towards the amount with an even last digit, so ties do not drift in one direction
+    HalfEven,
+    /// Away from zero
+    HalfUp,
+}
+
+/// Reasons an operation on `Money` fails
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MoneyError {
+    /// The amounts are in different currencies
+    CurrencyMismatch {
+        /// Currency of the left-hand amount
+        left: Currency,
+        /// Currency of the right-hand amount
+        right: Currency,
+    },
+    /// The text is not a decimal amount
+    InvalidAmount(String),
+    /// The result is too large to hold
+    Overflow,
+}
+
+// --- module struct definitinos ---
+
+/// An ISO 4217 currency with the number of decimal places of its minor unit
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Currency {
+    /// Three letter code
+    code: [u8; 3],
+    /// Decimal places of the minor unit, such as 2 for cents
+    minor_units: u32,
+}
+
+/// An amount of a currency, held as a whole number of millionths so sums are exact
+///
+/// Arithmetic between amounts in different currencies or past the range of the amount
+/// panics, and `try_add`, `try_sub` and `try_mul` report it as an error instead.
+/// Multiplying by an `f64`, such as a growth factor, rounds the exact product to the nearest
+/// millionth, half to even.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Money {
+    /// Amount in millionths of the currency
+    units: i128,
+    /// Currency of the amount
+    currency: Currency,
+}
+
+// --- module impl definitions ---
+
+impl Currency {
+    /// US dollar
+    pub const USD: Currency = Currency { code: *b"USD", minor_units: 2 };
+    /// Euro
+    pub const EUR: Currency = Currency { code: *b"EUR", minor_units: 2 };
+    /// Pound sterling
+    pub const GBP: Currency = Currency { code: *b"GBP", minor_units: 2 };
+    /// Swiss franc
+    pub const CHF: Currency = Currency { code: *b"CHF", minor_units: 2 };
+    /// Canadian dollar
+    pub const CAD: Currency = Currency { code: *b"CAD", minor_units: 2 };
+    /// Australian dollar
+    pub const AUD: Currency = Currency { code: *b"AUD", minor_units: 2 };
+    /// Chinese yuan
+    pub const CNY: Currency = Currency { code: *b"CNY", minor_units: 2 };
+    /// Japanese yen
+    pub const JPY: Currency = Currency { code: *b"JPY", minor_units: 0 };
+    /// Kuwaiti dinar
+    pub const KWD: Currency = Currency { code: *b"KWD", minor_units: 3 };
+
+    /// Create a `Currency` not in the known list
+    ///
+    ///  * `code` - Three upper case letters
+    ///  * `minor_units` - Decimal places of the minor unit, at most 6
+    ///  * return - The currency, or `None` if `code` or `minor_units` is invalid
+    ///
+    pub fn new(code: &str, minor_units: u32) -> Option<Currency> {
+        let bytes = code.as_bytes();
+        if bytes.len() != 3 || !bytes.iter().all(u8::is_ascii_uppercase) ||
+           minor_units > DECIMALS {
+            return None;
+        }
+        Some(Currency {
+                 code: [bytes[0], bytes[1], bytes[2]],
+                 minor_units,
+             })
+    }
+
+    /// The known currency with `code`
+    pub fn from_code(code: &str) -> Option<Currency> {
+        KNOWN_CURRENCIES.iter().find(|currency| currency.code() == code).cloned()
+    }
+
+    /// Three letter code of the currency
+    pub fn code(&self) -> &str {
+        ::std::str::from_utf8(&self.code).expect("codes are ASCII")
+    }
+
+    /// Decimal places of the minor unit
+    pub fn minor_units(&self) -> u32 {
+        self.minor_units
+    }
+}
+
+impl Money {
+    /// Create a zero amount of `currency`
+    pub fn zero(currency: Currency) -> Money {
+        Money { units: 0, currency }
+    }
+
+    /// Create `Money` from a count of minor units, such as cents
+    ///
+    ///  * `minor` - Number of minor units
+    ///  * `currency` - Currency of the amount
+    ///  * return - The amount
+    ///
+    pub fn from_minor(minor: i64, currency: Currency) -> Money {
+        Money {
+            units: minor as i128 * pow10(DECIMALS - currency.minor_units),
+            currency,
+        }
+    }
+
+    /// Create `Money` from an `f64` amount, rounded to the nearest millionth
+    pub fn from_f64(amount: f64, currency: Currency) -> Money {
+        Money { units: pow10(DECIMALS), currency } * amount
+    }
+
+    /// Create `Money` from a decimal amount such as `"-1234.5"`, without rounding
+    ///
+    ///  * `amount` - Optional sign, digits and optional fraction of at most six places
+    ///  * `currency` - Currency of the amount
+    ///  * return - The amount, or an error if `amount` is not a decimal amount
+    ///
+    pub fn parse(amount: &str, currency: Currency) -> Result<Money, MoneyError> {
+        // custom <fn money_parse>
+
+        let invalid = || MoneyError::InvalidAmount(amount.to_string());
+        let (negative, digits) = match amount.as_bytes().first() {
+            Some(b'-') => (true, &amount[1..]),
+            Some(b'+') => (false, &amount[1..]),
+            _ => (false, amount),
+        };
+        let (whole, fraction) = match digits.find('.') {
+            Some(point) => (&digits[..point], &digits[point + 1..]),
+            None => (digits, ""),
+        };
+        if whole.is_empty() && fraction.is_empty() || fraction.len() > DECIMALS as usize ||
+           !whole.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let mut units: i128 = 0;
+        for b in whole.bytes().chain(fraction.bytes()) {
+            units = units.checked_mul(10)
+                .and_then(|units| units.checked_add((b - b'0') as i128))
+                .ok_or_else(invalid)?;
+        }
+        units = units.checked_mul(pow10(DECIMALS - fraction.len() as u32))
+            .ok_or_else(invalid)?;
+        Ok(Money {
+               units: if negative { -units } else { units },
+               currency,
+           })
+
+        // end <fn money_parse>
+    }
+
+    /// Currency of the amount
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The amount as an `f64`, for reporting and charting
+    pub fn to_f64(&self) -> f64 {
+        self.units as f64 / pow10(DECIMALS) as f64
+    }
+
+    /// The amount rounded to the minor unit of its currency
+    pub fn round(&self, rounding: Rounding) -> Money {
+        self.round_dp(self.currency.minor_units, rounding)
+    }
+
+    /// The amount rounded to `places` decimal places
+    ///
+    ///  * `places` - Decimal places to keep
+    ///  * `rounding` - How ties are rounded
+    ///  * return - The rounded amount
+    ///
+    pub fn round_dp(&self, places: u32, rounding: Rounding) -> Money {
+        if places >= DECIMALS {
+            return *self;
+        }
+        let step = pow10(DECIMALS - places);
+        Money {
+            units: divide_rounded(self.units, step, rounding) * step,
+            currency: self.currency,
+        }
+    }
+
+    /// The amount as a count of minor units, such as cents
+    ///
+    ///  * `rounding` - How ties are rounded
+    ///  * return - The count of minor units, or an error if it does not fit an `i64`
+    ///
+    pub fn to_minor(&self, rounding: Rounding) -> Result<i64, MoneyError> {
+        i64::try_from(self.minor(rounding)).map_err(|_| MoneyError::Overflow)
+    }
+
+    /// Sum of `self` and `other`
+    ///
+    ///  * `other` - Amount to add
+    ///  * return - The sum, or an error if the currencies differ or the sum overflows
+    ///
+    pub fn try_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        let currency = self.same_currency(other)?;
+        let units = self.units.checked_add(other.units).ok_or(MoneyError::Overflow)?;
+        Ok(Money { units, currency })
+    }
+
+    /// Difference of `self` and `other`
+    ///
+    ///  * `other` - Amount to subtract
+    ///  * return - The difference, or an error if the currencies differ or the difference
+    ///    overflows
+    ///
+    pub fn try_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        let currency = self.same_currency(other)?;
+        let units = self.units.checked_sub(other.units).ok_or(MoneyError::Overflow)?;
+        Ok(Money { units, currency })
+    }
+
+    /// Product of `self` and `factor`, rounding the exact product to the nearest millionth
+    ///
+    ///  * `factor` - Amount to scale by, such as a growth factor
+    ///  * return - The product, or an error if it overflows or `factor` is not finite
+    ///
+    pub fn try_mul(&self, factor: f64) -> Result<Money, MoneyError> {
+        // custom <fn money_try_mul>
+
+        if !factor.is_finite() {
+            return Err(MoneyError::Overflow);
+        }
+        // Split factor exactly into mantissa * 2^exponent
+        let bits = factor.to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = (bits & 0x000f_ffff_ffff_ffff) as i128;
+        let mantissa = if biased == 0 {
+            fraction << 1
+        } else {
+            fraction | 0x0010_0000_0000_0000
+        };
+        let exponent = biased - 1075;
+        let signed = if bits >> 63 == 1 { -mantissa } else { mantissa };
+        let product = self.units.checked_mul(signed).ok_or(MoneyError::Overflow)?;
+
+        let units = if product == 0 || exponent <= -127 {
+            0
+        } else if exponent >= 0 {
+            // A shift of 127 or more leaves no positive power of two in an i128
+            1_i128.checked_shl(exponent as u32)
+                .filter(|&scale| scale > 0)
+                .and_then(|scale| product.checked_mul(scale))
+                .ok_or(MoneyError::Overflow)?
+        } else {
+            divide_rounded(product, 1 << -exponent, Rounding::HalfEven)
+        };
+        Ok(Money { units, currency: self.currency })
+
+        // end <fn money_try_mul>
+    }
+
+    // custom <impl Money>
+
+    /// The amount as a count of minor units, without limiting it to an `i64`
+    fn minor(&self, rounding: Rounding) -> i128 {
+        divide_rounded(self.units, pow10(DECIMALS - self.currency.minor_units), rounding)
+    }
+
+    /// The shared currency of `self` and `other`
+    fn same_currency(&self, other: &Money) -> Result<Currency, MoneyError> {
+        if self.currency == other.currency {
+            Ok(self.currency)
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                    left: self.currency,
+                    right: other.currency,
+                })
+        }
+    }
+
+    // end <impl Money>
+}
+
+/// Sum of amounts in the same currency, panicking if they differ
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        self.try_add(&rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+/// Difference of amounts in the same currency, panicking if they differ
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        self.try_sub(&rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+/// Scales the amount, rounding the exact product to the nearest millionth
+impl Mul<f64> for Money {
+    type Output = Money;
+
+    fn mul(self, rhs: f64) -> Money {
+        assert!(rhs.is_finite(), "cannot scale money by {}", rhs);
+        self.try_mul(rhs).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+/// Divides the amount, rounding to the nearest millionth
+impl Div<f64> for Money {
+    type Output = Money;
+
+    fn div(self, rhs: f64) -> Money {
+        self * (1.0 / rhs)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money {
+            units: self.units.checked_neg().expect("money overflow"),
+            currency: self.currency,
+        }
+    }
+}
+
+/// Amounts compare only within a currency
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        if self.currency == other.currency {
+            Some(self.units.cmp(&other.units))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Shows the amount rounded half to even to its minor unit, such as `-1234.57 USD`
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let places = self.currency.minor_units;
+        let minor = self.minor(Rounding::HalfEven);
+        let sign = if minor < 0 { "-" } else { "" };
+        let (magnitude, scale) = (minor.unsigned_abs(), pow10(places) as u128);
+        if places == 0 {
+            write!(f, "{}{} {}", sign, magnitude, self.currency)
+        } else {
+            write!(f,
+                   "{}{}.{:0width$} {}",
+                   sign,
+                   magnitude / scale,
+                   magnitude % scale,
+                   self.currency,
+                   width = places as usize)
+        }
+    }
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MoneyError::CurrencyMismatch { left, right } => {
+                write!(f, "cannot combine {} with {}", left, right)
+            }
+            MoneyError::InvalidAmount(ref amount) => write!(f, "invalid amount '{}'", amount),
+            MoneyError::Overflow => write!(f, "amount too large to hold"),
+        }
+    }
+}
+
+impl Error for MoneyError {}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Decimal places held by `Money`
+const DECIMALS: u32 = 6;
+
+/// Currencies found by `Currency::from_code`
+const KNOWN_CURRENCIES: [Currency; 9] = [Currency::USD,
+                                         Currency::EUR,
+                                         Currency::GBP,
+                                         Currency::CHF,
+                                         Currency::CAD,
+                                         Currency::AUD,
+                                         Currency::CNY,
+                                         Currency::JPY,
+                                         Currency::KWD];
+
+/// Ten to the power `exponent`
+fn pow10(exponent: u32) -> i128 {
+    10_i128.pow(exponent)
+}
+
+/// `numerator` divided by the positive `denominator`, with ties broken by `rounding`
+fn divide_rounded(numerator: i128, denominator: i128, rounding: Rounding) -> i128 {
+    let quotient = numerator.div_euclid(denominator);
+    let twice_remainder = 2 * numerator.rem_euclid(denominator);
+    match twice_remainder.cmp(&denominator) {
+        Ordering::Less => quotient,
+        Ordering::Greater => quotient + 1,
+        Ordering::Equal => {
+            match rounding {
+                Rounding::HalfEven => quotient + (quotient & 1),
+                Rounding::HalfUp if numerator >= 0 => quotient + 1,
+                Rounding::HalfUp => quotient,
+            }
+        }
+    }
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for money module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Date;
+    use RateCurve;
+    use TimeSeries;
+    use TimeValue;
+
+    fn usd(amount: &str) -> Money {
+        Money::parse(amount, Currency::USD).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(usd("-1234.5").to_string(), "-1234.50 USD");
+        assert_eq!(usd("0.005").to_string(), "0.00 USD");
+        assert_eq!(usd(".015").to_string(), "0.02 USD");
+        assert_eq!(Money::from_minor(1234, Currency::JPY).to_string(), "1234 JPY");
+        assert_eq!(Money::from_minor(1234, Currency::KWD),
+                   Money::parse("1.234", Currency::KWD).unwrap());
+        assert_eq!(Money::parse("1.2345678", Currency::USD),
+                   Err(MoneyError::InvalidAmount("1.2345678".to_string())));
+        assert!(Money::parse("1.2.3", Currency::USD).is_err());
+        assert!(Money::parse("-", Currency::USD).is_err());
+        assert_eq!(Currency::from_code("JPY"), Some(Currency::JPY));
+        assert_eq!(Currency::new("usd", 2), None);
+        assert_eq!(Currency::new("XAU", 4).unwrap().code(), "XAU");
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(usd("2.345").round(Rounding::HalfEven), usd("2.34"));
+        assert_eq!(usd("2.355").round(Rounding::HalfEven), usd("2.36"));
+        assert_eq!(usd("2.345").round(Rounding::HalfUp), usd("2.35"));
+        assert_eq!(usd("-2.345").round(Rounding::HalfUp), usd("-2.35"));
+        assert_eq!(usd("-2.345").round(Rounding::HalfEven), usd("-2.34"));
+        assert_eq!(usd("2.3449").round(Rounding::HalfUp), usd("2.34"));
+        assert_eq!(usd("1234.5").round_dp(0, Rounding::HalfEven), usd("1234"));
+        assert_eq!(usd("0.125").to_minor(Rounding::HalfUp), Ok(13));
+        let huge = Money::parse("100000000000000000000", Currency::USD).unwrap();
+        assert_eq!(huge.to_minor(Rounding::HalfEven), Err(MoneyError::Overflow));
+        assert_eq!(huge.to_string(), "100000000000000000000.00 USD");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let tenth = usd("0.1");
+        let mut total = Money::zero(Currency::USD);
+        for _ in 0..10 {
+            total = total + tenth;
+        }
+        assert_eq!(total, usd("1"));
+        assert_eq!(usd("100") * 1.05, usd("105"));
+        assert_eq!(usd("1").try_mul(2_f64.powi(200)), Err(MoneyError::Overflow));
+        assert_eq!(usd("1").try_mul(2_f64.powi(60)), Ok(usd("1152921504606846976")));
+        assert_eq!(usd("0").try_mul(2_f64.powi(200)), Ok(usd("0")));
+        assert_eq!(usd("1").try_mul(f64::INFINITY), Err(MoneyError::Overflow));
+        assert!(::std::panic::catch_unwind(|| usd("1") * 2_f64.powi(200)).is_err());
+        assert_eq!(usd("10") / 4.0, usd("2.5"));
+        assert_eq!(usd("1") / 3.0, usd("0.333333"));
+        assert_eq!(-usd("1") - usd("1"), usd("-2"));
+        assert_eq!(Money::from_f64(0.1, Currency::USD), tenth);
+        assert!(usd("1") < usd("2"));
+
+        let euros = Money::from_minor(100, Currency::EUR);
+        assert_eq!(usd("1").try_add(&euros),
+                   Err(MoneyError::CurrencyMismatch {
+                           left: Currency::USD,
+                           right: Currency::EUR,
+                       }));
+        assert_eq!(usd("1").partial_cmp(&euros), None);
+        assert!(::std::panic::catch_unwind(|| usd("1") - euros).is_err());
+
+        let largest = usd("170141183460469231731687303715884.105727");
+        assert_eq!(largest.try_add(&usd("0.000001")), Err(MoneyError::Overflow));
+        assert_eq!((-largest).try_sub(&usd("1")), Err(MoneyError::Overflow));
+        assert_eq!(largest.try_sub(&usd("1")).map(|m| m < largest), Ok(true));
+        let smallest = (-largest).try_sub(&usd("0.000001")).unwrap();
+        assert!(::std::panic::catch_unwind(|| -smallest).is_err());
+    }
+
+    #[test]
+    fn series_of_money() {
+        let date = |month| Date::from_ymd_opt(2017, month, 1).unwrap();
+        let flows = TimeSeries::new_summing(vec![TimeValue { time: date(1), value: usd("10.10") },
+                                                 TimeValue { time: date(1), value: usd("0.20") },
+                                                 TimeValue { time: date(7), value: usd("5") }]);
+        assert_eq!(flows[0].value, usd("10.30"));
+
+        let rates: TimeSeries<Date> =
+            vec![TimeValue { time: date(1), value: 0.05 }].into_iter().collect();
+        let moved = rates.revalue_on(flows[0], date(7));
+        assert_eq!(moved.time, date(7));
+        assert_eq!(moved.value.round(Rounding::HalfEven).to_string(), "10.56 USD");
+    }
+}
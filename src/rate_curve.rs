@@ -5,10 +5,14 @@
 
 // --- module use statements ---
 
+use Act365Fixed;
+use DayCount;
 use Time;
 use TimeSearchable;
 use TimeSeries;
+use TimeSeriesView;
 use TimeValue;
+use std::ops::Mul;
 use time_revalue::revalue_on;
 
 // --- module trait definitions ---
@@ -19,7 +23,7 @@ use time_revalue::revalue_on;
 /// Functionality provided includes moving values on time along the `RateCurve`
 /// and calculating the discount factor between points on the curve.
 ///
-trait RateCurve {
+pub trait RateCurve {
     /// Type for *Time*
     type T: Time<T = Self::T>;
 
@@ -29,26 +33,39 @@ trait RateCurve {
     ///  * `target_time` - Target time for revaluation
     ///  * return - Returns `TimeValue` representing `time_value` moved from it's time to `target_time`
     ///
-    fn revalue_on(&self,
-                  time_value: TimeValue<Self::T>,
-                  target_time: Self::T)
-                  -> TimeValue<Self::T>;
+    fn revalue_on<V>(&self,
+                     time_value: TimeValue<Self::T, V>,
+                     target_time: Self::T)
+                     -> TimeValue<Self::T, V>
+        where V: Mul<f64, Output = V> + Copy;
 
     /// Calculates the *discount* implied by *Self* to move $1.0 from *from* to *to*
     ///
     ///  * `from` - Start of discount period
     ///  * `to` - End of discount period
     ///  * return - Returns _discount_ such that _x_ * _discount_ represents
-    ///                   value of _x_ moved from *from* to *to* along curve
+    ///    value of _x_ moved from *from* to *to* along curve
     ///
     fn discount_from_to(&self, from: Self::T, to: Self::T) -> f64;
 
+    /// Calculates the *discount* implied by *Self* to move $1.0 from *from* to *to*,
+    /// measuring the years in each period of the curve with *day_count*
+    ///
+    ///  * `from` - Start of discount period
+    ///  * `to` - End of discount period
+    ///  * `day_count` - Convention used to measure the years between points on the curve
+    ///  * return - Returns _discount_ such that _x_ * _discount_ represents
+    ///    value of _x_ moved from *from* to *to* along curve
+    ///
+    fn discount_from_to_with<DC>(&self, from: Self::T, to: Self::T, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized;
+
     /// Merges *other* *RateCurve* with *Self* to produce new *RateCurve*
     ///
     ///  * `other` - `RateCurve` to merge with *self*
     ///  * return - TODO: document return
     ///
-    fn merge(self: &Self, other: &Self) -> Self;
+    fn merge(&self, other: &Self) -> Self;
 
     // custom <trait_rate_curve>
     // end <trait_rate_curve>
@@ -68,15 +85,17 @@ impl<T> RateCurve for TimeSeries<T>
     ///  * `target_time` - Target time for revaluation
     ///  * return - Returns `TimeValue` representing `time_value` moved from it's time to `target_time`
     ///
-    fn revalue_on(&self,
-                  time_value: TimeValue<Self::T>,
-                  target_time: Self::T)
-                  -> TimeValue<Self::T> {
+    fn revalue_on<V>(&self,
+                     time_value: TimeValue<Self::T, V>,
+                     target_time: Self::T)
+                     -> TimeValue<Self::T, V>
+        where V: Mul<f64, Output = V> + Copy
+    {
         // custom <fn rate_curve_time_series_t_revalue_on>
 
         TimeValue {
             time: target_time,
-            value: time_value.value * self.discount_from_to(time_value.time(), target_time),
+            value: time_value.value * self.discount_from_to(time_value.time, target_time),
         }
 
         // end <fn rate_curve_time_series_t_revalue_on>
@@ -87,15 +106,88 @@ impl<T> RateCurve for TimeSeries<T>
     ///  * `from` - Start of discount period
     ///  * `to` - End of discount period
     ///  * return - Returns _discount_ such that _x_ * _discount_ represents
-    ///                   value of _x_ moved from *from* to *to* along curve
+    ///    value of _x_ moved from *from* to *to* along curve
     ///
     fn discount_from_to(&self, from: Self::T, to: Self::T) -> f64 {
         // custom <fn rate_curve_time_series_t_discount_from_to>
+        self.discount_from_to_with(from, to, &Act365Fixed)
+        // end <fn rate_curve_time_series_t_discount_from_to>
+    }
+
+    /// Calculates the *discount* implied by *Self* to move $1.0 from *from* to *to*,
+    /// measuring the years in each period of the curve with *day_count*
+    ///
+    ///  * `from` - Start of discount period
+    ///  * `to` - End of discount period
+    ///  * `day_count` - Convention used to measure the years between points on the curve
+    ///  * return - Returns _discount_ such that _x_ * _discount_ represents
+    ///    value of _x_ moved from *from* to *to* along curve
+    ///
+    fn discount_from_to_with<DC>(&self, from: Self::T, to: Self::T, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn rate_curve_time_series_t_discount_from_to_with>
+        self.view().discount_from_to_with(from, to, day_count)
+        // end <fn rate_curve_time_series_t_discount_from_to_with>
+    }
+
+    /// Merges *other* *RateCurve* with *Self* to produce new *RateCurve*
+    ///
+    ///  * `other` - `RateCurve` to merge with *self*
+    ///  * return - TODO: document return
+    ///
+    fn merge(&self, _other: &Self) -> Self {
+        // custom <fn rate_curve_time_series_t_merge>
+        TimeSeries::default()
+        // end <fn rate_curve_time_series_t_merge>
+    }
+
+    // custom <impl RateCurve for TimeSeries<T>>
+    // end <impl RateCurve for TimeSeries<T>>
+}
+
+/// Discounting along the rates of a view, as by `RateCurve` for a series
+impl<'a, T> TimeSeriesView<'a, T>
+    where T: Time<T = T> + Ord
+{
+    /// Revalue `time_value` on `target_time` along the rates of the view
+    ///
+    ///  * `time_value` - A `TimeValue` to be revalued on `target_time`
+    ///  * `target_time` - Target time for revaluation
+    ///  * return - `time_value` moved from its time to `target_time`
+    ///
+    pub fn revalue_on<V>(&self, time_value: TimeValue<T, V>, target_time: T) -> TimeValue<T, V>
+        where V: Mul<f64, Output = V> + Copy
+    {
+        TimeValue {
+            time: target_time,
+            value: time_value.value * self.discount_from_to(time_value.time, target_time),
+        }
+    }
+
+    /// Discount along the rates of the view to move $1.0 from `from` to `to`
+    pub fn discount_from_to(&self, from: T, to: T) -> f64 {
+        self.discount_from_to_with(from, to, &Act365Fixed)
+    }
+
+    /// Discount along the rates of the view to move $1.0 from `from` to `to`, measuring the
+    /// years in each period with `day_count`
+    ///
+    ///  * `from` - Start of discount period
+    ///  * `to` - End of discount period
+    ///  * `day_count` - Convention used to measure the years between points on the view
+    ///  * return - Discount such that _x_ * discount is _x_ moved from `from` to `to`
+    ///
+    pub fn discount_from_to_with<DC>(&self, from: T, to: T, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_series_view_discount_from_to_with>
 
         if from > to {
-            1.0 / self.discount_from_to(to, from)
+            1.0 / self.discount_from_to_with(to, from, day_count)
         } else {
             let mut value = 1.0;
+            let mut start = from;
             let range = self.in_range(from..to);
             let mut rate = if range.start > 0 {
                 self[range.start - 1].value
@@ -104,28 +196,15 @@ impl<T> RateCurve for TimeSeries<T>
             };
 
             for i in range {
-                value = revalue_on(value, from, to, rate);
+                value = revalue_on(value, start, self[i].time, rate, day_count);
+                start = self[i].time;
                 rate = self[i].value;
             }
-            value
+            revalue_on(value, start, to, rate, day_count)
         }
 
-        // end <fn rate_curve_time_series_t_discount_from_to>
+        // end <fn time_series_view_discount_from_to_with>
     }
-
-    /// Merges *other* *RateCurve* with *Self* to produce new *RateCurve*
-    ///
-    ///  * `other` - `RateCurve` to merge with *self*
-    ///  * return - TODO: document return
-    ///
-    fn merge(self: &Self, other: &Self) -> Self {
-        // custom <fn rate_curve_time_series_t_merge>
-        TimeSeries { data: vec![] }
-        // end <fn rate_curve_time_series_t_merge>
-    }
-
-    // custom <impl RateCurve for TimeSeries<T>>
-    // end <impl RateCurve for TimeSeries<T>>
 }
 
 /// Test module for rate_curve module
@@ -137,7 +216,7 @@ mod tests {
         use super::*;
 
         #[test]
-        fn revalue_on() -> () {
+        fn revalue_on() {
             // custom <test fn rate_curve_time_series_t_revalue_on>
 
             let tv = TimeValue {
@@ -153,17 +232,78 @@ mod tests {
         }
 
         #[test]
-        fn discount_from_to() -> () {
+        fn discount_from_to() {
             // custom <test fn rate_curve_time_series_t_discount_from_to>
 
             println!("Discount on {:?} -> {:?}",
                      ts(),
                      ts().discount_from_to(1, 4));
+
+            assert_relative_eq!(ts().discount_from_to(1, 4), (0.03_f64 + 0.04 + 0.05).exp());
+            assert_relative_eq!(ts().discount_from_to(0, 2), 0.03_f64.exp());
+            assert_relative_eq!(ts().discount_from_to(4, 1),
+                                1.0 / (0.03_f64 + 0.04 + 0.05).exp());
             // end <test fn rate_curve_time_series_t_discount_from_to>
         }
 
         #[test]
-        fn merge() -> () {
+        fn discount_from_to_with() {
+            // custom <test fn rate_curve_time_series_t_discount_from_to_with>
+
+            use chrono::{TimeZone, Utc};
+            use {Act360, DateTime};
+
+            let jan = |year| Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
+            let curve: TimeSeries<DateTime> = TimeSeries::try_from_vec(vec![TimeValue {
+                                                                                time: jan(2017),
+                                                                                value: 0.03,
+                                                                            },
+                                                                            TimeValue {
+                                                                                time: jan(2018),
+                                                                                value: 0.04,
+                                                                            }]).unwrap();
+
+            assert_relative_eq!(curve.discount_from_to_with(jan(2017), jan(2019), &Act360),
+                                (0.03_f64 * 365.0 / 360.0 + 0.04 * 365.0 / 360.0).exp());
+            assert_relative_eq!(curve.discount_from_to(jan(2017), jan(2019)),
+                                (0.03_f64 + 0.04).exp());
+
+            // end <test fn rate_curve_time_series_t_discount_from_to_with>
+        }
+
+        #[test]
+        fn discount_from_to_date() {
+            // custom <test fn rate_curve_time_series_t_discount_from_to_date>
+
+            use {Act360, Date, DateSeries};
+
+            let date = |year, month, day| Date::from_ymd_opt(year, month, day).unwrap();
+            let curve: DateSeries = TimeSeries::try_from_vec(vec![TimeValue {
+                                                                      time: date(2017, 1, 1),
+                                                                      value: 0.03,
+                                                                  },
+                                                                  TimeValue {
+                                                                      time: date(2017, 7, 1),
+                                                                      value: 0.04,
+                                                                  }]).unwrap();
+
+            assert_relative_eq!(curve.discount_from_to_with(date(2017, 1, 1),
+                                                            date(2018, 1, 1),
+                                                            &Act360),
+                                (0.03_f64 * 181.0 / 360.0 + 0.04 * 184.0 / 360.0).exp());
+            assert_relative_eq!(curve.revalue_on(TimeValue {
+                                                     time: date(2018, 1, 1),
+                                                     value: 100.0,
+                                                 },
+                                                 date(2017, 1, 1))
+                                    .value,
+                                100.0 / curve.discount_from_to(date(2017, 1, 1), date(2018, 1, 1)));
+
+            // end <test fn rate_curve_time_series_t_discount_from_to_date>
+        }
+
+        #[test]
+        fn merge() {
             // custom <test fn rate_curve_time_series_t_merge>
             // end <test fn rate_curve_time_series_t_merge>
         }
@@ -171,24 +311,22 @@ mod tests {
         // custom <module ModuleCodeBlock.moduleBottom>
 
         fn ts() -> TimeSeries<Year> {
-            TimeSeries {
-                data: vec![TimeValue {
-                               time: 1,
-                               value: 0.03,
-                           },
-                           TimeValue {
-                               time: 2,
-                               value: 0.04,
-                           },
-                           TimeValue {
-                               time: 3,
-                               value: 0.05,
-                           },
-                           TimeValue {
-                               time: 4,
-                               value: 0.06,
-                           }],
-            }
+            TimeSeries::try_from_vec(vec![TimeValue {
+                                              time: 1,
+                                              value: 0.03,
+                                          },
+                                          TimeValue {
+                                              time: 2,
+                                              value: 0.04,
+                                          },
+                                          TimeValue {
+                                              time: 3,
+                                              value: 0.05,
+                                          },
+                                          TimeValue {
+                                              time: 4,
+                                              value: 0.06,
+                                          }]).unwrap()
         }
 
         // end <module ModuleCodeBlock.moduleBottom>
@@ -0,0 +1,600 @@
+//! Support for converting a `TimeSeries` between frequencies, such as daily data to annual
+//! totals or annual assumptions to monthly values
+
+// --- module use statements ---
+
+use Date;
+use FractionalYear;
+use Interpolation;
+use Range;
+use Time;
+use TimeSeries;
+use TimeValue;
+use Year;
+use YearMonth;
+use YearQuarter;
+use ZonedDateTime;
+use chrono::Datelike;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use zoned::from_local;
+
+// --- module enum definitions ---
+
+/// How the values falling in one bucket are reduced to a single value
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Aggregation {
+    /// Sum of the values
+    Sum,
+    /// Mean of the values, each counted once
+    Mean,
+    /// Earliest value
+    First,
+    /// Latest value
+    Last,
+    /// Smallest value
+    Min,
+    /// Largest value
+    Max,
+    /// Mean of the values, each held until the next value or the end of the bucket. The
+    /// start of the bucket takes the last value of the bucket before.
+    TimeWeightedMean,
+}
+
+/// How values are found for buckets finer than the data
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Upsample {
+    /// A bucket starting on a time of the data takes its value, others take the constant
+    Constant(f64),
+    /// Each bucket takes the value interpolated at its first day
+    Interpolate(Interpolation),
+}
+
+// --- module struct definitinos ---
+
+/// Open, high, low and close of the values falling in a bucket
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OhlcValue<B> {
+    /// Bucket summarized
+    pub time: B,
+    /// Earliest value
+    pub open: f64,
+    /// Largest value
+    pub high: f64,
+    /// Smallest value
+    pub low: f64,
+    /// Latest value
+    pub close: f64,
+}
+
+/// Buckets of one day
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Daily;
+
+/// Buckets of one calendar month
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Monthly;
+
+/// Buckets of one calendar quarter
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Quarterly;
+
+/// Buckets of one calendar year
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Yearly;
+
+/// Buckets of one fiscal year, named by the calendar year the fiscal year ends in
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FiscalYearly {
+    /// Month the fiscal year starts in, 1 through 12
+    start_month: u32,
+}
+
+// --- module trait definitions ---
+
+/// A time that falls on a calendar day
+pub trait CalendarTime: Time<T = Self> + Ord {
+    /// The date containing `self`
+    fn date(&self) -> Date;
+
+    /// The start of `date` as this time type
+    ///
+    ///  * `date` - Date to convert
+    ///  * return - The earliest time on `date`, in the zone of `self` for zoned times
+    ///
+    fn at_start_of(&self, date: &Date) -> Self;
+
+    // custom <trait_calendar_time>
+    // end <trait_calendar_time>
+}
+
+/// Rule dividing the calendar into consecutive buckets
+pub trait Bucketing {
+    /// Type naming each bucket
+    type Bucket: Time<T = Self::Bucket> + Ord;
+
+    /// The bucket containing `date`
+    fn bucket_of(&self, date: &Date) -> Self::Bucket;
+
+    /// The first date of `bucket`
+    fn first_date(&self, bucket: &Self::Bucket) -> Date;
+
+    /// The first date after `bucket`
+    fn end_date(&self, bucket: &Self::Bucket) -> Date;
+
+    // custom <trait_bucketing>
+    // end <trait_bucketing>
+}
+
+// --- module impl definitions ---
+
+/// A `Date` is its own day
+impl CalendarTime for Date {
+    fn date(&self) -> Date {
+        *self
+    }
+
+    fn at_start_of(&self, date: &Date) -> Self {
+        *date
+    }
+}
+
+/// A `ZonedDateTime` falls on its local date
+impl<Tz> CalendarTime for ZonedDateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Copy
+{
+    fn date(&self) -> Date {
+        self.naive_local().date()
+    }
+
+    fn at_start_of(&self, date: &Date) -> Self {
+        from_local(self, &date.and_time(NaiveTime::MIN))
+    }
+}
+
+/// A `Year` falls on its first day
+impl CalendarTime for Year {
+    fn date(&self) -> Date {
+        Date::from_ymd_opt(*self, 1, 1).expect("year within chrono range")
+    }
+
+    fn at_start_of(&self, date: &Date) -> Self {
+        date.year()
+    }
+}
+
+/// A `YearMonth` falls on its first day
+impl CalendarTime for YearMonth {
+    fn date(&self) -> Date {
+        self.first_day()
+    }
+
+    fn at_start_of(&self, date: &Date) -> Self {
+        YearMonth::from_date(date)
+    }
+}
+
+/// A `YearQuarter` falls on its first day
+impl CalendarTime for YearQuarter {
+    fn date(&self) -> Date {
+        self.first_day()
+    }
+
+    fn at_start_of(&self, date: &Date) -> Self {
+        YearQuarter::from_date(date)
+    }
+}
+
+/// A `FractionalYear` falls on the date containing it
+impl CalendarTime for FractionalYear {
+    fn date(&self) -> Date {
+        self.to_date().expect("year within chrono range")
+    }
+
+    fn at_start_of(&self, date: &Date) -> Self {
+        FractionalYear::from_date(date)
+    }
+}
+
+/// Days named by their `Date`
+impl Bucketing for Daily {
+    type Bucket = Date;
+
+    fn bucket_of(&self, date: &Date) -> Date {
+        *date
+    }
+
+    fn first_date(&self, bucket: &Date) -> Date {
+        *bucket
+    }
+
+    fn end_date(&self, bucket: &Date) -> Date {
+        bucket.succ_opt().expect("date within chrono range")
+    }
+}
+
+/// Months named by their `YearMonth`
+impl Bucketing for Monthly {
+    type Bucket = YearMonth;
+
+    fn bucket_of(&self, date: &Date) -> YearMonth {
+        YearMonth::from_date(date)
+    }
+
+    fn first_date(&self, bucket: &YearMonth) -> Date {
+        bucket.first_day()
+    }
+
+    fn end_date(&self, bucket: &YearMonth) -> Date {
+        YearMonth::from_months(bucket.months() + 1)
+            .expect("month before the last chrono date")
+            .first_day()
+    }
+}
+
+/// Quarters named by their `YearQuarter`
+impl Bucketing for Quarterly {
+    type Bucket = YearQuarter;
+
+    fn bucket_of(&self, date: &Date) -> YearQuarter {
+        YearQuarter::from_date(date)
+    }
+
+    fn first_date(&self, bucket: &YearQuarter) -> Date {
+        bucket.first_day()
+    }
+
+    fn end_date(&self, bucket: &YearQuarter) -> Date {
+        YearQuarter::from_quarters(bucket.quarters() + 1)
+            .expect("quarter before the last chrono date")
+            .first_day()
+    }
+}
+
+/// Calendar years named by their `Year`
+impl Bucketing for Yearly {
+    type Bucket = Year;
+
+    fn bucket_of(&self, date: &Date) -> Year {
+        date.year()
+    }
+
+    fn first_date(&self, bucket: &Year) -> Date {
+        bucket.date()
+    }
+
+    fn end_date(&self, bucket: &Year) -> Date {
+        (bucket + 1).date()
+    }
+}
+
+impl FiscalYearly {
+    /// Create a `FiscalYearly` rule
+    ///
+    ///  * `start_month` - Month the fiscal year starts in
+    ///  * return - The rule, or `None` if `start_month` is not 1 through 12
+    ///
+    pub fn new(start_month: u32) -> Option<FiscalYearly> {
+        if (1..=12).contains(&start_month) {
+            Some(FiscalYearly { start_month })
+        } else {
+            None
+        }
+    }
+
+    /// Month the fiscal year starts in
+    pub fn start_month(&self) -> u32 {
+        self.start_month
+    }
+}
+
+/// Fiscal years named by the `Year` they end in
+impl Bucketing for FiscalYearly {
+    type Bucket = Year;
+
+    fn bucket_of(&self, date: &Date) -> Year {
+        if self.start_month > 1 && date.month() >= self.start_month {
+            date.year() + 1
+        } else {
+            date.year()
+        }
+    }
+
+    fn first_date(&self, bucket: &Year) -> Date {
+        if self.start_month == 1 {
+            bucket.date()
+        } else {
+            Date::from_ymd_opt(bucket - 1, self.start_month, 1).expect("year within chrono range")
+        }
+    }
+
+    fn end_date(&self, bucket: &Year) -> Date {
+        self.first_date(&(bucket + 1))
+    }
+}
+
+impl<T> TimeSeries<T>
+    where T: CalendarTime
+{
+    /// Reduces the values falling in each bucket of `buckets` to one value
+    ///
+    ///  * `buckets` - Rule dividing the calendar into buckets
+    ///  * `aggregation` - How the values of a bucket are reduced
+    ///  * return - Series with a value for each bucket holding data
+    ///
+    pub fn resample<B>(&self, buckets: &B, aggregation: Aggregation) -> TimeSeries<B::Bucket>
+        where B: Bucketing
+    {
+        // custom <fn time_series_resample>
+
+        let groups = self.buckets(buckets);
+        let mut data = Vec::with_capacity(groups.len());
+        for (k, &(bucket, ref range)) in groups.iter().enumerate() {
+            let values = self[range.clone()].iter().map(|tv| tv.value);
+            let value = match aggregation {
+                Aggregation::Sum => values.sum(),
+                Aggregation::Mean => values.sum::<f64>() / range.len() as f64,
+                Aggregation::First => self[range.start].value,
+                Aggregation::Last => self[range.end - 1].value,
+                Aggregation::Min => values.fold(f64::INFINITY, f64::min),
+                Aggregation::Max => values.fold(f64::NEG_INFINITY, f64::max),
+                Aggregation::TimeWeightedMean => {
+                    let previous = if k > 0 {
+                        Some(&self[groups[k - 1].1.end - 1])
+                    } else {
+                        None
+                    };
+                    self.bucket_time_weighted_mean(buckets, &bucket, range.clone(), previous)
+                }
+            };
+            data.push(TimeValue {
+                          time: bucket,
+                          value,
+                      });
+        }
+        TimeSeries::from_sorted(data)
+
+        // end <fn time_series_resample>
+    }
+
+    /// Open, high, low and close of the values falling in each bucket of `buckets`
+    ///
+    ///  * `buckets` - Rule dividing the calendar into buckets
+    ///  * return - Summary of each bucket holding data, in time order
+    ///
+    pub fn resample_ohlc<B>(&self, buckets: &B) -> Vec<OhlcValue<B::Bucket>>
+        where B: Bucketing
+    {
+        // custom <fn time_series_resample_ohlc>
+        self.buckets(buckets)
+            .into_iter()
+            .map(|(time, range)| {
+                let values = &self[range];
+                OhlcValue {
+                    time,
+                    open: values[0].value,
+                    high: values.iter().map(|tv| tv.value).fold(f64::NEG_INFINITY, f64::max),
+                    low: values.iter().map(|tv| tv.value).fold(f64::INFINITY, f64::min),
+                    close: values[values.len() - 1].value,
+                }
+            })
+            .collect()
+        // end <fn time_series_resample_ohlc>
+    }
+
+    /// Spreads the series over every bucket of `buckets` from its first to its last time
+    ///
+    ///  * `buckets` - Rule dividing the calendar into buckets finer than the data
+    ///  * `upsample` - How the value of each bucket is found
+    ///  * return - Series with a value for each bucket, where the value can be found
+    ///
+    pub fn upsample<B>(&self, buckets: &B, upsample: Upsample) -> TimeSeries<B::Bucket>
+        where B: Bucketing
+    {
+        // custom <fn time_series_upsample>
+
+        let (first, last) = match (self.first(), self.last()) {
+            (Some(first), Some(last)) => (first.time.date(), last.time.date()),
+            _ => return TimeSeries::default(),
+        };
+        let by_date: TimeSeries<Date> = self.iter()
+            .map(|tv| {
+                     TimeValue {
+                         time: tv.time.date(),
+                         value: tv.value,
+                     }
+                 })
+            .collect();
+
+        let mut data = Vec::new();
+        let mut bucket = buckets.bucket_of(&first);
+        let end = buckets.bucket_of(&last);
+        while bucket <= end {
+            let date = buckets.first_date(&bucket);
+            let value = match upsample {
+                Upsample::Constant(fill) => {
+                    Some(by_date.binary_search_by(|tv| tv.time.cmp(&date))
+                             .map(|i| by_date[i].value)
+                             .unwrap_or(fill))
+                }
+                Upsample::Interpolate(interpolation) => by_date.value_at(date, interpolation),
+            };
+            if let Some(value) = value {
+                data.push(TimeValue {
+                              time: bucket,
+                              value,
+                          });
+            }
+            bucket = buckets.bucket_of(&buckets.end_date(&bucket));
+        }
+        TimeSeries::from_sorted(data)
+
+        // end <fn time_series_upsample>
+    }
+
+    // custom <impl TimeSeries>
+
+    /// Each bucket holding data with the range of its items
+    fn buckets<B>(&self, buckets: &B) -> Vec<(B::Bucket, Range<usize>)>
+        where B: Bucketing
+    {
+        let mut groups: Vec<(B::Bucket, Range<usize>)> = Vec::new();
+        for (i, tv) in self.iter().enumerate() {
+            let bucket = buckets.bucket_of(&tv.time.date());
+            match groups.last_mut() {
+                Some(&mut (last, ref mut range)) if last == bucket => range.end = i + 1,
+                _ => groups.push((bucket, i..i + 1)),
+            }
+        }
+        groups
+    }
+
+    /// Mean of the items in `range`, each held until the next or the end of `bucket`
+    fn bucket_time_weighted_mean<B>(&self,
+                                    buckets: &B,
+                                    bucket: &B::Bucket,
+                                    range: Range<usize>,
+                                    previous: Option<&TimeValue<T>>)
+                                    -> f64
+        where B: Bucketing
+    {
+        let reference = self[range.start].time;
+        let start = reference.at_start_of(&buckets.first_date(bucket));
+        let end = reference.at_start_of(&buckets.end_date(bucket));
+
+        let (mut total, mut weights) = (0.0, 0.0);
+        if let Some(previous) = previous {
+            let weight = start.years_until(&reference).max(0.0);
+            total += previous.value * weight;
+            weights += weight;
+        }
+        for i in range.clone() {
+            let until = if i + 1 < range.end { self[i + 1].time } else { end };
+            let weight = self[i].time.years_until(&until).max(0.0);
+            total += self[i].value * weight;
+            weights += weight;
+        }
+
+        if weights > 0.0 {
+            total / weights
+        } else {
+            self[range.clone()].iter().map(|tv| tv.value).sum::<f64>() / range.len() as f64
+        }
+    }
+
+    // end <impl TimeSeries>
+}
+
+/// Test module for resample module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use DateTime;
+    use chrono::Utc;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn daily() -> TimeSeries<Date> {
+        [(date(2016, 12, 31), 1.0),
+         (date(2017, 1, 1), 2.0),
+         (date(2017, 1, 21), 6.0),
+         (date(2017, 7, 1), 4.0),
+         (date(2017, 12, 31), 3.0)]
+            .iter()
+            .map(|&(time, value)| TimeValue { time, value })
+            .collect()
+    }
+
+    fn values<B: Time<T = B>>(series: &TimeSeries<B>) -> Vec<(B, f64)> {
+        series.iter().map(|tv| (tv.time, tv.value)).collect()
+    }
+
+    #[test]
+    fn aggregations() {
+        let daily = daily();
+        assert_eq!(values(&daily.resample(&Yearly, Aggregation::Sum)),
+                   vec![(2016, 1.0), (2017, 15.0)]);
+        assert_eq!(values(&daily.resample(&Yearly, Aggregation::Mean)),
+                   vec![(2016, 1.0), (2017, 3.75)]);
+        assert_eq!(values(&daily.resample(&Yearly, Aggregation::First)),
+                   vec![(2016, 1.0), (2017, 2.0)]);
+        assert_eq!(values(&daily.resample(&Yearly, Aggregation::Last)),
+                   vec![(2016, 1.0), (2017, 3.0)]);
+        assert_eq!(values(&daily.resample(&Yearly, Aggregation::Min)),
+                   vec![(2016, 1.0), (2017, 2.0)]);
+        assert_eq!(values(&daily.resample(&Yearly, Aggregation::Max)),
+                   vec![(2016, 1.0), (2017, 6.0)]);
+        assert_eq!(daily.resample(&Quarterly, Aggregation::Sum).len(), 4);
+        assert_eq!(values(&daily.resample(&Monthly, Aggregation::Sum))[1],
+                   (YearMonth::new(2017, 1).unwrap(), 8.0));
+
+        let ohlc = daily.resample_ohlc(&Yearly);
+        assert_eq!(ohlc[1],
+                   OhlcValue {
+                       time: 2017,
+                       open: 2.0,
+                       high: 6.0,
+                       low: 2.0,
+                       close: 3.0,
+                   });
+    }
+
+    #[test]
+    fn time_weighted_mean() {
+        let daily = daily();
+        let annual = daily.resample(&Yearly, Aggregation::TimeWeightedMean);
+        assert_eq!(annual[0].value, 1.0);
+        assert_relative_eq!(annual[1].value,
+                            (2.0 * 20.0 + 6.0 * 161.0 + 4.0 * 183.0 + 3.0) / 365.0);
+
+        let hourly: TimeSeries<DateTime> = [(0, 1.0), (6, 3.0), (30, 5.0)]
+            .iter()
+            .map(|&(hours, value)| {
+                     TimeValue {
+                         time: Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap() +
+                               ::Duration::hours(hours),
+                         value,
+                     }
+                 })
+            .collect();
+        let days = hourly.resample(&Daily, Aggregation::TimeWeightedMean);
+        assert_relative_eq!(days[0].value, (6.0 + 3.0 * 18.0) / 24.0);
+        assert_relative_eq!(days[1].value, (3.0 * 6.0 + 5.0 * 18.0) / 24.0);
+    }
+
+    #[test]
+    fn fiscal_years() {
+        let fiscal = FiscalYearly::new(7).unwrap();
+        assert_eq!(values(&daily().resample(&fiscal, Aggregation::Sum)),
+                   vec![(2017, 9.0), (2018, 7.0)]);
+        assert_eq!(fiscal.first_date(&2018), date(2017, 7, 1));
+        assert_eq!(fiscal.end_date(&2018), date(2018, 7, 1));
+        assert_eq!(FiscalYearly::new(1).unwrap().bucket_of(&date(2017, 12, 31)), 2017);
+        assert!(FiscalYearly::new(13).is_none());
+    }
+
+    #[test]
+    fn upsample() {
+        let annual: TimeSeries<Year> = vec![TimeValue {
+                                                time: 2017,
+                                                value: 12.0,
+                                            },
+                                            TimeValue {
+                                                time: 2018,
+                                                value: 24.0,
+                                            }]
+            .into_iter()
+            .collect();
+        let monthly = annual.upsample(&Monthly, Upsample::Constant(0.0));
+        assert_eq!(monthly.len(), 13);
+        assert_eq!((monthly[0].value, monthly[1].value, monthly[12].value), (12.0, 0.0, 24.0));
+
+        let stepped = annual.upsample(&Monthly, Upsample::Interpolate(Interpolation::StepPrevious));
+        assert!(stepped[..12].iter().all(|tv| tv.value == 12.0));
+        let linear = annual.upsample(&Quarterly, Upsample::Interpolate(Interpolation::Linear));
+        assert_relative_eq!(linear[2].value, 12.0 + 12.0 * 181.0 / 365.0);
+    }
+}
@@ -0,0 +1,471 @@
+//! Support for statistics over a trailing window of a `TimeSeries`, such as a 12 month mean
+
+// --- module use statements ---
+
+use Tenor;
+use TimeSearchable;
+use TimeSeries;
+use TimeStep;
+use TimeValue;
+use std::cmp::Ordering;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+
+// --- module enum definitions ---
+
+/// The observations a rolling statistic covers at each time
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Window {
+    /// The given number of observations ending at the time. Times with fewer observations
+    /// up to them have a `NaN` value.
+    Count(usize),
+    /// Observations after the time moved back by the tenor, up to and including the time
+    Span(Tenor),
+}
+
+/// Statistic computed over each window
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RollingStat {
+    /// Mean of the values
+    Mean,
+    /// Sum of the values
+    Sum,
+    /// Sample standard deviation of the values, needing two or more
+    Std,
+    /// Smallest value
+    Min,
+    /// Largest value
+    Max,
+    /// Value at the given fraction between the smallest and largest, interpolating linearly
+    /// between ranks
+    Quantile(f64),
+}
+
+// --- module struct definitinos ---
+
+/// Incrementally maintained statistic of the values in a window
+struct RollingState {
+    /// Statistic maintained
+    stat: RollingStat,
+    /// Values in the window
+    count: usize,
+    /// Mean of the values, for the standard deviation
+    mean: f64,
+    /// Sum of squared differences from `mean`
+    sum_squares: f64,
+    /// Sum of the values
+    sum: f64,
+    /// Indices of candidate extremes with values in monotone order
+    extremes: VecDeque<(usize, f64)>,
+    /// Window values split at the quantile rank
+    quantile: RollingQuantile,
+}
+
+/// Window values split into the values up to the quantile rank and those above it
+///
+/// Values leave the window oldest first, so a value is removed by moving `first` past its
+/// index and dropping it once it reaches the top of its heap.
+struct RollingQuantile {
+    /// Fraction of the way from the smallest to the largest value
+    q: f64,
+    /// Values at or below the quantile rank, largest on top
+    lower: BinaryHeap<Ranked>,
+    /// Values above the quantile rank, smallest on top
+    upper: BinaryHeap<Reverse<Ranked>>,
+    /// Values of `lower` still in the window
+    lower_count: usize,
+    /// Values of `upper` still in the window
+    upper_count: usize,
+    /// Index of the oldest value still in the window
+    first: usize,
+}
+
+/// A value ordered by value, then by its index in the series
+#[derive(Debug, Clone, Copy)]
+struct Ranked {
+    /// Value in the window
+    value: f64,
+    /// Index of the value in the series
+    index: usize,
+}
+
+// --- module impl definitions ---
+
+impl<T> TimeSeries<T>
+    where T: TimeStep + Ord
+{
+    /// Computes `stat` over the window ending at each time of the series
+    ///
+    /// Each value enters and leaves the window once, so sums, means, standard deviations,
+    /// minimums and maximums take O(n). Quantiles take O(n log w) for windows of up to w values,
+    /// keeping the window in two heaps split at the quantile rank.
+    ///
+    ///  * `window` - Observations covered at each time
+    ///  * `stat` - Statistic to compute
+    ///  * return - Series with the statistic at each time, `NaN` where the window does not
+    ///    hold enough observations, or `None` if `window` is a span the times cannot be
+    ///    moved back by
+    ///
+    pub fn rolling(&self, window: Window, stat: RollingStat) -> Option<TimeSeries<T>> {
+        // custom <fn time_series_rolling>
+
+        let starts = self.window_starts(window)?;
+        let required = match (window, stat) {
+            (Window::Count(count), _) => count.max(1),
+            (_, RollingStat::Std) => 2,
+            _ => 1,
+        };
+        let mut state = RollingState::new(stat);
+        let mut data = Vec::with_capacity(self.len());
+        let mut start = 0;
+        for (i, tv) in self.iter().enumerate() {
+            state.push(i, tv.value);
+            while start < starts[i] {
+                state.pop(start, self[start].value);
+                start += 1;
+            }
+            let value = if i + 1 - start >= required {
+                state.value()
+            } else {
+                f64::NAN
+            };
+            data.push(TimeValue {
+                          time: tv.time,
+                          value,
+                      });
+        }
+        Some(TimeSeries::from_sorted(data))
+
+        // end <fn time_series_rolling>
+    }
+
+    // custom <impl TimeSeries>
+
+    /// Index of the first observation in the window ending at each time, or `None` if a
+    /// time cannot be moved back by the span of `window`
+    fn window_starts(&self, window: Window) -> Option<Vec<usize>> {
+        match window {
+            Window::Count(count) => {
+                Some((0..self.len()).map(|i| (i + 1).saturating_sub(count)).collect())
+            }
+            Window::Span(span) => {
+                self.iter()
+                    .map(|tv| tv.time.sub_period(&span).map(|opens| self.after(opens).start))
+                    .collect()
+            }
+        }
+    }
+
+    // end <impl TimeSeries>
+}
+
+impl RollingState {
+    /// Create an empty `RollingState` for `stat`
+    fn new(stat: RollingStat) -> RollingState {
+        RollingState {
+            stat,
+            count: 0,
+            mean: 0.0,
+            sum_squares: 0.0,
+            sum: 0.0,
+            extremes: VecDeque::new(),
+            quantile: RollingQuantile::new(match stat {
+                                               RollingStat::Quantile(q) => q,
+                                               _ => 0.5,
+                                           }),
+        }
+    }
+
+    /// Adds the value at `index` to the window
+    fn push(&mut self, index: usize, value: f64) {
+        self.count += 1;
+        match self.stat {
+            RollingStat::Mean | RollingStat::Sum => self.sum += value,
+            RollingStat::Std => {
+                // Welford's update
+                let delta = value - self.mean;
+                self.mean += delta / self.count as f64;
+                self.sum_squares += delta * (value - self.mean);
+            }
+            RollingStat::Min | RollingStat::Max => {
+                let is_min = self.stat == RollingStat::Min;
+                let dominated = |last: f64| if is_min { last >= value } else { last <= value };
+                while self.extremes.back().is_some_and(|&(_, last)| dominated(last)) {
+                    self.extremes.pop_back();
+                }
+                self.extremes.push_back((index, value));
+            }
+            RollingStat::Quantile(_) => self.quantile.push(index, value),
+        }
+    }
+
+    /// Removes the value at `index`, the oldest in the window
+    fn pop(&mut self, index: usize, value: f64) {
+        self.count -= 1;
+        match self.stat {
+            RollingStat::Mean | RollingStat::Sum => self.sum -= value,
+            RollingStat::Std => {
+                if self.count == 0 {
+                    self.mean = 0.0;
+                    self.sum_squares = 0.0;
+                } else {
+                    let delta = value - self.mean;
+                    self.mean -= delta / self.count as f64;
+                    self.sum_squares -= delta * (value - self.mean);
+                }
+            }
+            RollingStat::Min | RollingStat::Max => {
+                if self.extremes.front().is_some_and(|&(first, _)| first == index) {
+                    self.extremes.pop_front();
+                }
+            }
+            RollingStat::Quantile(_) => self.quantile.pop(index, value),
+        }
+    }
+
+    /// Statistic of the values in the window
+    fn value(&self) -> f64 {
+        match self.stat {
+            RollingStat::Sum => self.sum,
+            RollingStat::Mean => self.sum / self.count as f64,
+            RollingStat::Std => (self.sum_squares.max(0.0) / (self.count - 1) as f64).sqrt(),
+            RollingStat::Min | RollingStat::Max => {
+                self.extremes.front().map_or(f64::NAN, |&(_, value)| value)
+            }
+            RollingStat::Quantile(_) => self.quantile.value(),
+        }
+    }
+}
+
+impl RollingQuantile {
+    /// Create an empty `RollingQuantile` for the quantile `q`
+    fn new(q: f64) -> RollingQuantile {
+        RollingQuantile {
+            q: q.clamp(0.0, 1.0),
+            lower: BinaryHeap::new(),
+            upper: BinaryHeap::new(),
+            lower_count: 0,
+            upper_count: 0,
+            first: 0,
+        }
+    }
+
+    /// Adds the value at `index` to the window
+    fn push(&mut self, index: usize, value: f64) {
+        let ranked = Ranked { value, index };
+        if self.lower.peek().is_some_and(|top| ranked < *top) {
+            self.lower.push(ranked);
+            self.lower_count += 1;
+        } else {
+            self.upper.push(Reverse(ranked));
+            self.upper_count += 1;
+        }
+        self.rebalance();
+    }
+
+    /// Removes the value at `index`, the oldest in the window
+    fn pop(&mut self, index: usize, value: f64) {
+        // Every value in `lower` is below every value in `upper`, removed or not
+        if self.lower.peek().is_some_and(|top| Ranked { value, index } <= *top) {
+            self.lower_count -= 1;
+        } else {
+            self.upper_count -= 1;
+        }
+        self.first = index + 1;
+        self.rebalance();
+    }
+
+    /// Rank of the quantile in the window, from 0 for the smallest value
+    fn rank(&self) -> f64 {
+        self.q * (self.lower_count + self.upper_count).saturating_sub(1) as f64
+    }
+
+    /// Moves values between the heaps until `lower` holds those up to the quantile rank,
+    /// leaving values still in the window on top of both
+    fn rebalance(&mut self) {
+        let target = if self.lower_count + self.upper_count == 0 {
+            0
+        } else {
+            self.rank().floor() as usize + 1
+        };
+        self.prune();
+        while self.lower_count > target {
+            let Ranked { value, index } = self.lower.pop().expect("lower holds its count");
+            self.upper.push(Reverse(Ranked { value, index }));
+            self.lower_count -= 1;
+            self.upper_count += 1;
+            self.prune();
+        }
+        while self.lower_count < target {
+            let Reverse(ranked) = self.upper.pop().expect("upper holds its count");
+            self.lower.push(ranked);
+            self.upper_count -= 1;
+            self.lower_count += 1;
+            self.prune();
+        }
+        self.compact();
+    }
+
+    /// Drops removed values from the tops of the heaps
+    fn prune(&mut self) {
+        let first = self.first;
+        while self.lower.peek().is_some_and(|top| top.index < first) {
+            self.lower.pop();
+        }
+        while self.upper.peek().is_some_and(|top| top.0.index < first) {
+            self.upper.pop();
+        }
+    }
+
+    /// Drops removed values below the tops once they outnumber the values in the window, so
+    /// the heaps stay O(w)
+    fn compact(&mut self) {
+        let first = self.first;
+        if self.lower.len() > 2 * self.lower_count + 16 {
+            self.lower.retain(|ranked| ranked.index >= first);
+        }
+        if self.upper.len() > 2 * self.upper_count + 16 {
+            self.upper.retain(|ranked| ranked.0.index >= first);
+        }
+    }
+
+    /// Quantile of the values in the window
+    fn value(&self) -> f64 {
+        let rank = self.rank();
+        let low = self.lower.peek().map_or(f64::NAN, |top| top.value);
+        if rank.fract() == 0.0 {
+            return low;
+        }
+        let high = self.upper.peek().map_or(f64::NAN, |top| top.0.value);
+        low + (high - low) * rank.fract()
+    }
+}
+
+/// Order values from smallest to largest, breaking ties by index so each is distinct
+impl Ord for Ranked {
+    fn cmp(&self, other: &Ranked) -> Ordering {
+        self.value.total_cmp(&other.value).then(self.index.cmp(&other.index))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Ranked) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Ranked) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+/// Test module for rolling module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Date;
+    use Duration;
+    use Year;
+
+    fn series(values: &[f64]) -> TimeSeries<Year> {
+        values.iter()
+            .enumerate()
+            .map(|(i, &value)| TimeValue { time: 2000 + i as Year, value })
+            .collect()
+    }
+
+    fn values<T: TimeStep>(series: &TimeSeries<T>) -> Vec<f64> {
+        series.iter().map(|tv| tv.value).collect()
+    }
+
+    /// The statistic recomputed from scratch over each window, to check the incremental one
+    fn brute_force(values: &[f64], count: usize, stat: RollingStat) -> Vec<f64> {
+        (0..values.len())
+            .map(|i| {
+                if i + 1 < count {
+                    return f64::NAN;
+                }
+                let mut window = values[i + 1 - count..i + 1].to_vec();
+                window.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let n = window.len() as f64;
+                let mean = window.iter().sum::<f64>() / n;
+                match stat {
+                    RollingStat::Mean => mean,
+                    RollingStat::Sum => mean * n,
+                    RollingStat::Std => {
+                        (window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+                    }
+                    RollingStat::Min => window[0],
+                    RollingStat::Max => window[window.len() - 1],
+                    RollingStat::Quantile(q) => {
+                        let rank = q * (n - 1.0);
+                        let low = window[rank.floor() as usize];
+                        let high = window[rank.ceil() as usize];
+                        low + (high - low) * rank.fract()
+                    }
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn count_windows() {
+        let raw = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0, 5.0, 3.0, 5.0];
+        let s = series(&raw);
+        for &stat in &[RollingStat::Mean,
+                       RollingStat::Sum,
+                       RollingStat::Std,
+                       RollingStat::Min,
+                       RollingStat::Max,
+                       RollingStat::Quantile(0.5)] {
+            let rolled = values(&s.rolling(Window::Count(3), stat).unwrap());
+            let expected = brute_force(&raw, 3, stat);
+            assert!(rolled[..2].iter().all(|v| v.is_nan()));
+            for (a, b) in rolled[2..].iter().zip(&expected[2..]) {
+                assert_relative_eq!(*a, *b, epsilon = 1e-12);
+            }
+        }
+        assert_eq!(s.rolling(Window::Count(3), RollingStat::Sum).unwrap()[2].time, 2002);
+    }
+
+    #[test]
+    fn quantiles() {
+        // Many repeated values, so removals must take out the right copy
+        let raw: Vec<f64> = (0..200).map(|i| ((i * 37) % 11) as f64).collect();
+        let s = series(&raw);
+        for &count in &[1, 2, 5, 24] {
+            for &q in &[0.0, 0.1, 0.25, 0.5, 0.9, 1.0] {
+                let stat = RollingStat::Quantile(q);
+                let rolled = values(&s.rolling(Window::Count(count), stat).unwrap());
+                let expected = brute_force(&raw, count, stat);
+                for (a, b) in rolled[count - 1..].iter().zip(&expected[count - 1..]) {
+                    assert_relative_eq!(*a, *b, epsilon = 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn span_windows() {
+        let start = Date::from_ymd_opt(2017, 1, 1).unwrap();
+        let irregular: TimeSeries<Date> = [(0, 1.0), (1, 2.0), (2, 3.0), (10, 4.0), (11, 5.0)]
+            .iter()
+            .map(|&(days, value)| TimeValue { time: start + Duration::days(days), value })
+            .collect();
+        let week: Tenor = "1W".parse().unwrap();
+        let rolled = |stat| values(&irregular.rolling(Window::Span(week), stat).unwrap());
+        assert_eq!(rolled(RollingStat::Sum), vec![1.0, 3.0, 6.0, 4.0, 9.0]);
+        assert_eq!(rolled(RollingStat::Max), vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(rolled(RollingStat::Quantile(0.5)), vec![1.0, 1.5, 2.0, 4.0, 4.5]);
+        let std = rolled(RollingStat::Std);
+        assert!(std[0].is_nan() && std[3].is_nan());
+        assert_relative_eq!(std[4], 0.5_f64.sqrt());
+
+        let half_year = Window::Span("6M".parse().unwrap());
+        assert!(series(&[1.0, 2.0]).rolling(half_year, RollingStat::Sum).is_none());
+    }
+}
@@ -0,0 +1,268 @@
+//! Support for generating regular schedules of times, such as coupon or contribution dates
+
+// --- module use statements ---
+
+use Calendar;
+use Roll;
+use Rollable;
+use Tenor;
+use TenorShift;
+use Time;
+use TimeSeries;
+use TimeValue;
+
+// --- module enum definitions ---
+
+/// Where an irregular period goes when the frequency does not divide the schedule evenly
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Stub {
+    /// A first period shorter than the frequency, with regular periods counted back from the end
+    ShortFront,
+    /// A first period longer than the frequency, with regular periods counted back from the end
+    LongFront,
+    /// A last period shorter than the frequency, with regular periods counted on from the start
+    ShortBack,
+    /// A last period longer than the frequency, with regular periods counted on from the start
+    LongBack,
+}
+
+// --- module struct definitinos ---
+
+/// Describes a schedule of times from `start` to `end` at a regular `frequency`
+#[derive(Debug, Clone, Copy)]
+pub struct Schedule<'a, T> {
+    /// First time of the schedule
+    pub start: T,
+    /// Last time of the schedule
+    pub end: T,
+    /// Period between regular times
+    pub frequency: Tenor,
+    /// Placement of any irregular period
+    pub stub: Stub,
+    /// Day of the month regular times fall on, clamped to the end of shorter months
+    pub roll_day: Option<u32>,
+    /// Calendar and convention used to move times onto business days
+    pub calendar: Option<(&'a dyn Calendar, Roll)>,
+}
+
+// --- module impl definitions ---
+
+impl<'a, T> Schedule<'a, T>
+    where T: Time<T = T> + TenorShift + Rollable + Ord
+{
+    /// Create a `Schedule` with a short front stub, no roll day and no calendar
+    ///
+    ///  * `start` - First time of the schedule
+    ///  * `end` - Last time of the schedule
+    ///  * `frequency` - Period between regular times
+    ///  * return - The schedule
+    ///
+    pub fn new(start: T, end: T, frequency: Tenor) -> Schedule<'a, T> {
+        Schedule {
+            start,
+            end,
+            frequency,
+            stub: Stub::ShortFront,
+            roll_day: None,
+            calendar: None,
+        }
+    }
+
+    /// Places any irregular period according to `stub`
+    pub fn stub(mut self, stub: Stub) -> Schedule<'a, T> {
+        self.stub = stub;
+        self
+    }
+
+    /// Puts regular times on `roll_day` of the month
+    pub fn roll_day(mut self, roll_day: u32) -> Schedule<'a, T> {
+        self.roll_day = Some(roll_day);
+        self
+    }
+
+    /// Moves times onto business days of `calendar` according to `roll`
+    pub fn calendar(mut self, calendar: &'a dyn Calendar, roll: Roll) -> Schedule<'a, T> {
+        self.calendar = Some((calendar, roll));
+        self
+    }
+
+    /// Generates the times of the schedule
+    ///
+    ///  * return - Ordered, distinct times from `start` to `end`, or `None` if `start` is
+    ///    after `end`, `frequency` is not positive or cannot move the time type
+    ///
+    pub fn times(&self) -> Option<Vec<T>> {
+        // custom <fn schedule_times>
+
+        if self.start > self.end || self.frequency.count <= 0 {
+            return None;
+        }
+
+        let backward = match self.stub {
+            Stub::ShortFront | Stub::LongFront => true,
+            Stub::ShortBack | Stub::LongBack => false,
+        };
+        let (anchor, limit) = if backward {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        };
+        let step = if backward { -1 } else { 1 };
+
+        // Each regular time is measured from the anchor so month-end clamping does not drift
+        let mut regular = Vec::new();
+        for i in 1.. {
+            let mut time = anchor.add_tenor(&self.frequency.times(step * i)?, false)?;
+            if let Some(roll_day) = self.roll_day {
+                time = time.on_day_of_month(roll_day);
+            }
+            if (backward && time <= limit) || (!backward && time >= limit) {
+                let has_stub = time != limit;
+                if has_stub && (self.stub == Stub::LongFront || self.stub == Stub::LongBack) {
+                    regular.pop();
+                }
+                break;
+            }
+            regular.push(time);
+        }
+
+        let mut times = Vec::with_capacity(regular.len() + 2);
+        times.push(limit);
+        times.extend(regular.into_iter().rev());
+        times.push(anchor);
+        if !backward {
+            times.reverse();
+        }
+
+        if let Some((calendar, roll)) = self.calendar {
+            for time in times.iter_mut() {
+                *time = time.roll(calendar, roll);
+            }
+            // Rolling keeps any time of day, so a rolled time can pass the next one
+            times.sort();
+        }
+        times.dedup();
+        Some(times)
+
+        // end <fn schedule_times>
+    }
+
+    /// Generates a series with a zero value at each time of the schedule
+    ///
+    ///  * return - Series ready to be filled, or `None` if the times cannot be generated
+    ///
+    pub fn time_series(&self) -> Option<TimeSeries<T>> {
+        // custom <fn schedule_time_series>
+        self.times().map(|times| {
+            TimeSeries::from_sorted(times.into_iter()
+                                        .map(|time| TimeValue { time, value: 0.0 })
+                                        .collect())
+        })
+        // end <fn schedule_time_series>
+    }
+}
+
+/// Test module for schedule module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Date;
+    use HolidayRule;
+    use RuleCalendar;
+    use WeekendsOnly;
+    use YearMonth;
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn tenor(s: &str) -> Tenor {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn regular() {
+        let times = Schedule::new(date(2017, 1, 15), date(2018, 1, 15), tenor("3M"))
+            .times()
+            .unwrap();
+        assert_eq!(times,
+                   vec![date(2017, 1, 15),
+                        date(2017, 4, 15),
+                        date(2017, 7, 15),
+                        date(2017, 10, 15),
+                        date(2018, 1, 15)]);
+    }
+
+    #[test]
+    fn stubs() {
+        let schedule = Schedule::new(date(2017, 2, 1), date(2018, 1, 15), tenor("3M"));
+        assert_eq!(schedule.times().unwrap(),
+                   vec![date(2017, 2, 1),
+                        date(2017, 4, 15),
+                        date(2017, 7, 15),
+                        date(2017, 10, 15),
+                        date(2018, 1, 15)]);
+        assert_eq!(schedule.stub(Stub::LongFront).times().unwrap(),
+                   vec![date(2017, 2, 1),
+                        date(2017, 7, 15),
+                        date(2017, 10, 15),
+                        date(2018, 1, 15)]);
+        assert_eq!(schedule.stub(Stub::ShortBack).times().unwrap(),
+                   vec![date(2017, 2, 1),
+                        date(2017, 5, 1),
+                        date(2017, 8, 1),
+                        date(2017, 11, 1),
+                        date(2018, 1, 15)]);
+        assert_eq!(schedule.stub(Stub::LongBack).times().unwrap(),
+                   vec![date(2017, 2, 1),
+                        date(2017, 5, 1),
+                        date(2017, 8, 1),
+                        date(2018, 1, 15)]);
+    }
+
+    #[test]
+    fn roll_day_and_calendar() {
+        let schedule = Schedule::new(date(2017, 1, 31), date(2017, 6, 30), tenor("1M"))
+            .stub(Stub::ShortBack)
+            .roll_day(31);
+        assert_eq!(schedule.times().unwrap(),
+                   vec![date(2017, 1, 31),
+                        date(2017, 2, 28),
+                        date(2017, 3, 31),
+                        date(2017, 4, 30),
+                        date(2017, 5, 31),
+                        date(2017, 6, 30)]);
+
+        let calendar = RuleCalendar::new(vec![HolidayRule::OneOff(date(2017, 5, 31))]);
+        assert_eq!(schedule.calendar(&calendar, Roll::ModifiedFollowing).times().unwrap(),
+                   vec![date(2017, 1, 31),
+                        date(2017, 2, 28),
+                        date(2017, 3, 31),
+                        date(2017, 4, 28),
+                        date(2017, 5, 30),
+                        date(2017, 6, 30)]);
+
+
+        let at = |day, hour| Utc.with_ymd_and_hms(2017, 7, day, hour, 0, 0).unwrap();
+        let weekend = Schedule::new(at(8, 23), at(10, 1), tenor("1D")).stub(Stub::ShortBack);
+        assert_eq!(weekend.calendar(&WeekendsOnly, Roll::Following).times().unwrap(),
+                   vec![at(10, 1), at(10, 23)]);
+    }
+
+    #[test]
+    fn time_series() {
+        let series = Schedule::new(YearMonth::new(2017, 1).unwrap(),
+                                   YearMonth::new(2017, 12).unwrap(),
+                                   tenor("1M"))
+            .time_series()
+            .unwrap();
+        assert_eq!(series.len(), 12);
+        assert!(series.iter().all(|tv| tv.value == 0.0));
+        assert!(Schedule::new(2017, 2020, tenor("6M")).times().is_none());
+        assert!(Schedule::new(2020, 2017, tenor("1Y")).times().is_none());
+        assert_eq!(Schedule::new(2017, 2020, tenor("1Y")).times(),
+                   Some(vec![2017, 2018, 2019, 2020]));
+    }
+}
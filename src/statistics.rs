@@ -0,0 +1,342 @@
+//! Support for summary statistics of a `TimeSeries` or a view of part of it
+//!
+//! Missing values, those that are `NaN`, are left out of every statistic.
+
+// --- module use statements ---
+
+use Time;
+use TimeSeries;
+use TimeSeriesView;
+use TimeValue;
+
+// --- module struct definitinos ---
+
+/// A fall in value from a peak to a later trough
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Drawdown<T>
+    where T: Time<T = T>
+{
+    /// Largest value before the trough
+    pub peak: TimeValue<T>,
+    /// Smallest value after the peak
+    pub trough: TimeValue<T>,
+}
+
+// --- module impl definitions ---
+
+impl<'a, T> TimeSeriesView<'a, T>
+    where T: Time<T = T> + Ord
+{
+    /// Number of values that are not missing
+    pub fn count(&self) -> usize {
+        self.present().count()
+    }
+
+    /// Mean of the values
+    pub fn mean(&self) -> Option<f64> {
+        let count = self.count();
+        if count == 0 {
+            None
+        } else {
+            Some(self.present().map(|tv| tv.value).sum::<f64>() / count as f64)
+        }
+    }
+
+    /// Mean of the values, each held until the next value, over the years from the first time
+    /// to the last
+    ///
+    ///  * return - The mean, the only value if there is one, or `None` if there are none
+    ///
+    pub fn time_weighted_mean(&self) -> Option<f64> {
+        // custom <fn time_series_view_time_weighted_mean>
+
+        let present: Vec<&TimeValue<T>> = self.present().collect();
+        let (first, last) = (present.first()?, present.last()?);
+        let span = first.time.years_until(&last.time);
+        if span == 0.0 {
+            return Some(first.value);
+        }
+        let weighted: f64 = present.windows(2)
+            .map(|pair| pair[0].value * pair[0].time.years_until(&pair[1].time))
+            .sum();
+        Some(weighted / span)
+
+        // end <fn time_series_view_time_weighted_mean>
+    }
+
+    /// Sample variance of the values, dividing by one less than the count
+    ///
+    ///  * return - The variance, or `None` with fewer than two values
+    ///
+    pub fn variance(&self) -> Option<f64> {
+        let (count, m2, _, _) = self.central_moments()?;
+        if count < 2.0 {
+            None
+        } else {
+            Some(m2 * count / (count - 1.0))
+        }
+    }
+
+    /// Sample standard deviation of the values, the square root of `variance`
+    pub fn std(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Sample skewness of the values, adjusted for bias as by spreadsheet `SKEW`
+    ///
+    ///  * return - The skewness, or `None` with fewer than three values or none distinct
+    ///
+    pub fn skew(&self) -> Option<f64> {
+        // custom <fn time_series_view_skew>
+        let (n, m2, m3, _) = self.central_moments()?;
+        if n < 3.0 || m2 == 0.0 {
+            return None;
+        }
+        Some(m3 / m2.powf(1.5) * (n * (n - 1.0)).sqrt() / (n - 2.0))
+        // end <fn time_series_view_skew>
+    }
+
+    /// Sample excess kurtosis of the values, adjusted for bias as by spreadsheet `KURT`, so a
+    /// normal distribution has zero
+    ///
+    ///  * return - The kurtosis, or `None` with fewer than four values or none distinct
+    ///
+    pub fn kurtosis(&self) -> Option<f64> {
+        // custom <fn time_series_view_kurtosis>
+        let (n, m2, _, m4) = self.central_moments()?;
+        if n < 4.0 || m2 == 0.0 {
+            return None;
+        }
+        let excess = m4 / (m2 * m2) - 3.0;
+        Some(((n + 1.0) * excess + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)))
+        // end <fn time_series_view_kurtosis>
+    }
+
+    /// Value at fraction `q` between the smallest and largest, interpolating linearly between
+    /// ranks
+    ///
+    ///  * `q` - Fraction from 0 for the smallest value to 1 for the largest
+    ///  * return - The quantile, or `None` if there are no values
+    ///
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        // custom <fn time_series_view_quantile>
+
+        let mut sorted: Vec<f64> = self.present().map(|tv| tv.value).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).expect("missing values are left out"));
+        let rank = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+        let (low, high) = (sorted[rank.floor() as usize], sorted[rank.ceil() as usize]);
+        Some(low + (high - low) * rank.fract())
+
+        // end <fn time_series_view_quantile>
+    }
+
+    /// The smallest value with its time, the earliest if it repeats
+    pub fn argmin(&self) -> Option<TimeValue<T>> {
+        self.present().fold(None, |min: Option<TimeValue<T>>, tv| match min {
+            Some(min) if min.value <= tv.value => Some(min),
+            _ => Some(*tv),
+        })
+    }
+
+    /// The largest value with its time, the earliest if it repeats
+    pub fn argmax(&self) -> Option<TimeValue<T>> {
+        self.present().fold(None, |max: Option<TimeValue<T>>, tv| match max {
+            Some(max) if max.value >= tv.value => Some(max),
+            _ => Some(*tv),
+        })
+    }
+
+    /// Largest fall from a peak to a later trough, as a fraction of the peak, for positive
+    /// values such as prices or balances
+    ///
+    ///  * return - The drawdown, or `None` if the values never fall
+    ///
+    pub fn max_drawdown(&self) -> Option<Drawdown<T>> {
+        // custom <fn time_series_view_max_drawdown>
+
+        let mut peak: Option<TimeValue<T>> = None;
+        let mut deepest: Option<Drawdown<T>> = None;
+        for tv in self.present() {
+            match peak {
+                Some(high) if high.value >= tv.value => {
+                    let drawdown = Drawdown {
+                        peak: high,
+                        trough: *tv,
+                    };
+                    if tv.value < high.value &&
+                       deepest.is_none_or(|deepest| drawdown.fraction() > deepest.fraction()) {
+                        deepest = Some(drawdown);
+                    }
+                }
+                _ => peak = Some(*tv),
+            }
+        }
+        deepest
+
+        // end <fn time_series_view_max_drawdown>
+    }
+
+    // custom <impl TimeSeriesView>
+
+    /// The values that are not missing
+    fn present(&self) -> impl Iterator<Item = &'a TimeValue<T>> {
+        self.into_iter().filter(|tv| !tv.value.is_nan())
+    }
+
+    /// Count and second to fourth central moments of the values, each divided by the count
+    fn central_moments(&self) -> Option<(f64, f64, f64, f64)> {
+        let mean = self.mean()?;
+        let count = self.count() as f64;
+        let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+        for tv in self.present() {
+            let d = tv.value - mean;
+            m2 += d * d;
+            m3 += d * d * d;
+            m4 += d * d * d * d;
+        }
+        Some((count, m2 / count, m3 / count, m4 / count))
+    }
+
+    // end <impl TimeSeriesView>
+}
+
+impl<T> TimeSeries<T>
+    where T: Time<T = T> + Ord
+{
+    /// Number of values that are not missing
+    pub fn count(&self) -> usize {
+        self.view().count()
+    }
+
+    /// Mean of the values
+    pub fn mean(&self) -> Option<f64> {
+        self.view().mean()
+    }
+
+    /// Mean of the values, each held until the next value, over the years from the first time
+    /// to the last
+    pub fn time_weighted_mean(&self) -> Option<f64> {
+        self.view().time_weighted_mean()
+    }
+
+    /// Sample variance of the values, dividing by one less than the count
+    pub fn variance(&self) -> Option<f64> {
+        self.view().variance()
+    }
+
+    /// Sample standard deviation of the values
+    pub fn std(&self) -> Option<f64> {
+        self.view().std()
+    }
+
+    /// Sample skewness of the values, adjusted for bias as by spreadsheet `SKEW`
+    pub fn skew(&self) -> Option<f64> {
+        self.view().skew()
+    }
+
+    /// Sample excess kurtosis of the values, adjusted for bias as by spreadsheet `KURT`
+    pub fn kurtosis(&self) -> Option<f64> {
+        self.view().kurtosis()
+    }
+
+    /// Value at fraction `q` between the smallest and largest, interpolating linearly between
+    /// ranks
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.view().quantile(q)
+    }
+
+    /// The smallest value with its time, the earliest if it repeats
+    pub fn argmin(&self) -> Option<TimeValue<T>> {
+        self.view().argmin()
+    }
+
+    /// The largest value with its time, the earliest if it repeats
+    pub fn argmax(&self) -> Option<TimeValue<T>> {
+        self.view().argmax()
+    }
+
+    /// Largest fall from a peak to a later trough, as a fraction of the peak
+    pub fn max_drawdown(&self) -> Option<Drawdown<T>> {
+        self.view().max_drawdown()
+    }
+}
+
+impl<T> Drawdown<T>
+    where T: Time<T = T>
+{
+    /// Fall in value from the peak to the trough
+    pub fn amount(&self) -> f64 {
+        self.peak.value - self.trough.value
+    }
+
+    /// Fall in value as a fraction of the peak
+    pub fn fraction(&self) -> f64 {
+        self.amount() / self.peak.value
+    }
+}
+
+/// Test module for statistics module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Year;
+
+    fn series(values: &[f64]) -> TimeSeries<Year> {
+        values.iter()
+            .enumerate()
+            .map(|(i, &value)| TimeValue { time: 2000 + i as Year, value })
+            .collect()
+    }
+
+    #[test]
+    fn moments() {
+        let s = series(&[1.0, 2.0, f64::NAN, 3.0, 4.0, 10.0]);
+        assert_eq!(s.count(), 5);
+        assert_eq!(s.mean(), Some(4.0));
+        assert_relative_eq!(s.variance().unwrap(), 12.5);
+        assert_relative_eq!(s.std().unwrap(), 12.5_f64.sqrt());
+        assert_relative_eq!(s.skew().unwrap(), 1.697056, epsilon = 1e-6);
+        assert_relative_eq!(s.kurtosis().unwrap(), 3.152, epsilon = 1e-12);
+
+        assert_eq!(series(&[]).mean(), None);
+        assert_eq!(series(&[1.0]).variance(), None);
+        assert_eq!(series(&[2.0, 2.0, 2.0]).skew(), None);
+    }
+
+    #[test]
+    fn order_statistics() {
+        let s = series(&[3.0, 1.0, 4.0, 1.0, 5.0]);
+        assert_eq!(s.quantile(0.5), Some(3.0));
+        assert_eq!(s.quantile(0.1), Some(1.0));
+        assert_eq!(s.quantile(0.9), Some(4.6));
+        assert_eq!(s.argmin(), Some(TimeValue { time: 2001, value: 1.0 }));
+        assert_eq!(s.argmax(), Some(TimeValue { time: 2004, value: 5.0 }));
+        assert_eq!(series(&[f64::NAN]).argmax(), None);
+    }
+
+    #[test]
+    fn time_weighted() {
+        let s: TimeSeries<Year> = [(2000, 1.0), (2001, 3.0), (2004, 5.0)]
+            .iter()
+            .map(|&(time, value)| TimeValue { time, value })
+            .collect();
+        assert_relative_eq!(s.time_weighted_mean().unwrap(), (1.0 + 3.0 * 3.0) / 4.0);
+        assert_eq!(s.view_on_or_after(2004).time_weighted_mean(), Some(5.0));
+    }
+
+    #[test]
+    fn drawdown() {
+        let s = series(&[100.0, 120.0, 90.0, 110.0, 60.0, 200.0, 150.0]);
+        let deepest = s.max_drawdown().unwrap();
+        assert_eq!((deepest.peak.time, deepest.trough.time), (2001, 2004));
+        assert_relative_eq!(deepest.fraction(), 0.5);
+        assert_eq!(deepest.amount(), 60.0);
+
+        let later = s.view_on_or_after(2005).max_drawdown().unwrap();
+        assert_eq!((later.peak.value, later.trough.value), (200.0, 150.0));
+        assert_eq!(series(&[1.0, 2.0, 2.0]).max_drawdown(), None);
+    }
+}
@@ -0,0 +1,462 @@
+//! Support for tenors, periods such as *3M* or *10Y* that move times forward or back
+
+// --- module use statements ---
+
+use Add;
+use Date;
+use DuplicatePolicy;
+use Duration;
+use Time;
+use TimeSeries;
+use TimeValue;
+use Year;
+use YearMonth;
+use YearQuarter;
+use ZonedDateTime;
+use chrono::Datelike;
+use chrono::TimeZone;
+use std::error::Error;
+use std::fmt;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::str::FromStr;
+use zoned::on_local_date;
+
+// --- module enum definitions ---
+
+/// Unit of a `Tenor`
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TenorUnit {
+    /// Calendar days, written *D*
+    Days,
+    /// Weeks of seven days, written *W*
+    Weeks,
+    /// Calendar months, written *M*
+    Months,
+    /// Calendar years, written *Y*
+    Years,
+}
+
+// --- module struct definitinos ---
+
+/// A period of time such as *2W*, *3M* or *10Y*
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Tenor {
+    /// Number of `unit`s in the period, negative to move back in time
+    pub count: i32,
+    /// Unit of the period
+    pub unit: TenorUnit,
+}
+
+/// Error from parsing a `Tenor` from a string
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseTenorError {
+    /// The string that failed to parse
+    pub input: String,
+}
+
+// --- module trait definitions ---
+
+/// A time that can be moved by a `Tenor`
+pub trait TenorShift: Sized {
+    /// Moves `self` by `tenor`
+    ///
+    /// When a month or year tenor lands on a day past the end of the month, the result is
+    /// the last day of that month.
+    ///
+    ///  * `tenor` - Period to move by
+    ///  * `end_of_month` - If true and `self` is the last day of its month, month and year
+    ///    tenors land on the last day of the resulting month
+    ///  * return - `self` moved by `tenor`, or `None` if `tenor` is finer than the time type
+    ///    can represent or the result is out of range
+    ///
+    fn add_tenor(&self, tenor: &Tenor, end_of_month: bool) -> Option<Self>;
+
+    /// Moves `self` onto `day` of its month, or the last day of the month if it is shorter
+    ///
+    /// Times without a day of the month are returned unchanged.
+    ///
+    ///  * `day` - Day of the month
+    ///  * return - `self` on `day` of its month
+    ///
+    fn on_day_of_month(&self, _day: u32) -> Self
+        where Self: Copy
+    {
+        *self
+    }
+
+    // custom <trait_tenor_shift>
+    // end <trait_tenor_shift>
+}
+
+// --- module impl definitions ---
+
+impl Tenor {
+    /// Create a `Tenor`
+    ///
+    ///  * `count` - Number of `unit`s in the period
+    ///  * `unit` - Unit of the period
+    ///  * return - The tenor
+    ///
+    pub fn new(count: i32, unit: TenorUnit) -> Tenor {
+        Tenor { count, unit }
+    }
+
+    /// Whole number of months in the tenor
+    ///
+    ///  * return - Months in the tenor, or `None` for day and week tenors or if the count
+    ///    of months overflows
+    ///
+    pub fn months(&self) -> Option<i32> {
+        // custom <fn tenor_months>
+        match self.unit {
+            TenorUnit::Months => Some(self.count),
+            TenorUnit::Years => self.count.checked_mul(12),
+            TenorUnit::Days | TenorUnit::Weeks => None,
+        }
+        // end <fn tenor_months>
+    }
+
+    /// The tenor repeated `times` times
+    ///
+    ///  * `times` - Number of repetitions
+    ///  * return - Tenor with `count` multiplied by `times`, or `None` if the count overflows
+    ///
+    pub fn times(&self, times: i32) -> Option<Tenor> {
+        self.count.checked_mul(times).map(|count| Tenor::new(count, self.unit))
+    }
+
+    /// The tenor in the opposite direction
+    ///
+    ///  * return - Tenor with `count` negated, or `None` if the count overflows
+    ///
+    pub fn checked_neg(&self) -> Option<Tenor> {
+        self.count.checked_neg().map(|count| Tenor::new(count, self.unit))
+    }
+}
+
+impl fmt::Display for Tenor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unit = match self.unit {
+            TenorUnit::Days => 'D',
+            TenorUnit::Weeks => 'W',
+            TenorUnit::Months => 'M',
+            TenorUnit::Years => 'Y',
+        };
+        write!(f, "{}{}", self.count, unit)
+    }
+}
+
+/// Parse tenors such as *1D*, *2W*, *3M*, *10Y* or *-6M*, ignoring case
+impl FromStr for Tenor {
+    type Err = ParseTenorError;
+
+    fn from_str(s: &str) -> Result<Tenor, ParseTenorError> {
+        // custom <fn tenor_from_str>
+
+        let error = || ParseTenorError { input: s.to_string() };
+        let trimmed = s.trim();
+        let unit = match trimmed.chars().last().map(|c| c.to_ascii_uppercase()) {
+            Some('D') => TenorUnit::Days,
+            Some('W') => TenorUnit::Weeks,
+            Some('M') => TenorUnit::Months,
+            Some('Y') => TenorUnit::Years,
+            _ => return Err(error()),
+        };
+        let count = &trimmed[..trimmed.len() - 1];
+        if count.is_empty() || count.starts_with('+') {
+            return Err(error());
+        }
+        count.parse().map(|count| Tenor::new(count, unit)).map_err(|_| error())
+
+        // end <fn tenor_from_str>
+    }
+}
+
+/// Panics if `count` is `i32::MIN`, which has no negation
+impl Neg for Tenor {
+    type Output = Tenor;
+
+    fn neg(self) -> Tenor {
+        Tenor::new(-self.count, self.unit)
+    }
+}
+
+impl fmt::Display for ParseTenorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid tenor `{}`, expected a count and one of D, W, M or Y", self.input)
+    }
+}
+
+impl Error for ParseTenorError {}
+
+/// Move a `Date` by a `Tenor`
+impl TenorShift for Date {
+    fn add_tenor(&self, tenor: &Tenor, end_of_month: bool) -> Option<Self> {
+        // custom <fn tenor_shift_date_add_tenor>
+
+        match tenor.unit {
+            TenorUnit::Days => self.checked_add_signed(Duration::days(i64::from(tenor.count))),
+            TenorUnit::Weeks => self.checked_add_signed(Duration::weeks(i64::from(tenor.count))),
+            TenorUnit::Months | TenorUnit::Years => {
+                let month = YearMonth::from_date(self);
+                let target = YearMonth::from_months(month.months()
+                                                        .checked_add(tenor.months()?)?)?;
+                let last_day = target.last_day();
+                if (end_of_month && *self == month.last_day()) || self.day() > last_day.day() {
+                    Some(last_day)
+                } else {
+                    last_day.with_day(self.day())
+                }
+            }
+        }
+
+        // end <fn tenor_shift_date_add_tenor>
+    }
+
+    fn on_day_of_month(&self, day: u32) -> Self {
+        let last_day = YearMonth::from_date(self).last_day();
+        last_day.with_day(day).unwrap_or(last_day)
+    }
+}
+
+/// Move the local date of a `ZonedDateTime` by a `Tenor`, keeping its time of day
+impl<Tz> TenorShift for ZonedDateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Copy
+{
+    fn add_tenor(&self, tenor: &Tenor, end_of_month: bool) -> Option<Self> {
+        self.naive_local()
+            .date()
+            .add_tenor(tenor, end_of_month)
+            .map(|date| on_local_date(self, &date))
+    }
+
+    fn on_day_of_month(&self, day: u32) -> Self {
+        on_local_date(self, &self.naive_local().date().on_day_of_month(day))
+    }
+}
+
+/// Move a `Year` by a `Tenor` of whole years
+impl TenorShift for Year {
+    fn add_tenor(&self, tenor: &Tenor, _end_of_month: bool) -> Option<Self> {
+        tenor.months().and_then(|months| if months % 12 == 0 {
+                                    self.checked_add(months / 12)
+                                } else {
+                                    None
+                                })
+    }
+}
+
+/// Move a `YearMonth` by a `Tenor` of whole months
+impl TenorShift for YearMonth {
+    fn add_tenor(&self, tenor: &Tenor, _end_of_month: bool) -> Option<Self> {
+        tenor.months()
+            .and_then(|months| self.months().checked_add(months))
+            .and_then(YearMonth::from_months)
+    }
+}
+
+/// Move a `YearQuarter` by a `Tenor` of whole quarters
+impl TenorShift for YearQuarter {
+    fn add_tenor(&self, tenor: &Tenor, _end_of_month: bool) -> Option<Self> {
+        tenor.months().and_then(|months| if months % 3 == 0 {
+                                    self.quarters()
+                                        .checked_add(months / 3)
+                                        .and_then(YearQuarter::from_quarters)
+                                } else {
+                                    None
+                                })
+    }
+}
+
+/// Panics if the result is outside the dates chrono can represent
+impl Add<Tenor> for Date {
+    type Output = Date;
+
+    fn add(self, rhs: Tenor) -> Date {
+        self.add_tenor(&rhs, false).expect("date within chrono range")
+    }
+}
+
+impl Sub<Tenor> for Date {
+    type Output = Date;
+
+    fn sub(self, rhs: Tenor) -> Date {
+        self + -rhs
+    }
+}
+
+/// Panics if the result is outside the dates chrono can represent
+impl<Tz> Add<Tenor> for ZonedDateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Copy
+{
+    type Output = ZonedDateTime<Tz>;
+
+    fn add(self, rhs: Tenor) -> ZonedDateTime<Tz> {
+        self.add_tenor(&rhs, false).expect("date within chrono range")
+    }
+}
+
+impl<Tz> Sub<Tenor> for ZonedDateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Copy
+{
+    type Output = ZonedDateTime<Tz>;
+
+    fn sub(self, rhs: Tenor) -> ZonedDateTime<Tz> {
+        self + -rhs
+    }
+}
+
+/// Panics unless `rhs` is a whole number of months
+impl Add<Tenor> for YearMonth {
+    type Output = YearMonth;
+
+    fn add(self, rhs: Tenor) -> YearMonth {
+        self.add_tenor(&rhs, false).expect("tenor of whole months within chrono range")
+    }
+}
+
+/// Panics unless `rhs` is a whole number of months
+impl Sub<Tenor> for YearMonth {
+    type Output = YearMonth;
+
+    fn sub(self, rhs: Tenor) -> YearMonth {
+        self + -rhs
+    }
+}
+
+/// Panics unless `rhs` is a whole number of quarters
+impl Add<Tenor> for YearQuarter {
+    type Output = YearQuarter;
+
+    fn add(self, rhs: Tenor) -> YearQuarter {
+        self.add_tenor(&rhs, false).expect("tenor of whole quarters within chrono range")
+    }
+}
+
+/// Panics unless `rhs` is a whole number of quarters
+impl Sub<Tenor> for YearQuarter {
+    type Output = YearQuarter;
+
+    fn sub(self, rhs: Tenor) -> YearQuarter {
+        self + -rhs
+    }
+}
+
+impl<T> TimeSeries<T>
+    where T: Time<T = T> + TenorShift + Ord
+{
+    /// Create a series with times given by tenors from `anchor`, such as the pillars of a
+    /// rate curve
+    ///
+    ///  * `anchor` - Time the tenors are measured from
+    ///  * `points` - Tenor and value of each point in the series
+    ///  * return - Series ordered by time, or `None` if a tenor cannot move `anchor` or two
+    ///    tenors give the same time
+    ///
+    pub fn from_tenors(anchor: T, points: &[(Tenor, f64)]) -> Option<TimeSeries<T>> {
+        // custom <fn time_series_from_tenors>
+
+        let data = points.iter()
+            .map(|&(tenor, value)| {
+                     anchor.add_tenor(&tenor, false).map(|time| TimeValue { time, value })
+                 })
+            .collect::<Option<Vec<_>>>()?;
+        TimeSeries::new(data, DuplicatePolicy::Error).ok()
+
+        // end <fn time_series_from_tenors>
+    }
+}
+
+/// Test module for tenor module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn tenor(s: &str) -> Tenor {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(tenor("3M"), Tenor::new(3, TenorUnit::Months));
+        assert_eq!(tenor("10y"), Tenor::new(10, TenorUnit::Years));
+        assert_eq!(tenor("-2W"), Tenor::new(-2, TenorUnit::Weeks));
+        assert_eq!(tenor("1D").to_string(), "1D");
+        assert_eq!(tenor("-6M").to_string(), "-6M");
+        assert!("M".parse::<Tenor>().is_err());
+        assert!("3".parse::<Tenor>().is_err());
+        assert!("3Q".parse::<Tenor>().is_err());
+        assert_eq!(tenor("200000000Y").months(), None);
+        assert_eq!(tenor("3M").times(4), Some(tenor("12M")));
+        assert_eq!(tenor("3M").times(i32::MAX), None);
+        assert_eq!(tenor("3M").checked_neg(), Some(tenor("-3M")));
+        assert_eq!(tenor("-2147483648D").checked_neg(), None);
+        assert_eq!("x1Y".parse::<Tenor>(),
+                   Err(ParseTenorError { input: "x1Y".to_string() }));
+    }
+
+    #[test]
+    fn add_to_date() {
+        assert_eq!(date(2017, 10, 17) + tenor("2W"), date(2017, 10, 31));
+        assert_eq!(date(2017, 10, 17) - tenor("1D"), date(2017, 10, 16));
+        assert_eq!(date(2017, 10, 17) + tenor("10Y"), date(2027, 10, 17));
+        assert_eq!(date(2017, 1, 31) + tenor("1M"), date(2017, 2, 28));
+        assert_eq!(date(2016, 2, 29) + tenor("1Y"), date(2017, 2, 28));
+        assert_eq!(date(2017, 2, 28) + tenor("1M"), date(2017, 3, 28));
+        assert_eq!(date(2017, 2, 28).add_tenor(&tenor("1M"), true),
+                   Some(date(2017, 3, 31)));
+        assert_eq!(date(2017, 3, 31) - tenor("3M"), date(2016, 12, 31));
+        assert_eq!(date(2017, 2, 10).on_day_of_month(31), date(2017, 2, 28));
+        assert_eq!(date(2017, 3, 10).on_day_of_month(15), date(2017, 3, 15));
+        assert_eq!(date(2017, 10, 17).add_tenor(&tenor("300000Y"), false), None);
+        assert_eq!(date(2017, 10, 17).add_tenor(&tenor("-2000000000M"), false), None);
+    }
+
+    #[test]
+    fn add_to_other_times() {
+        let noon = Utc.with_ymd_and_hms(2017, 1, 31, 12, 0, 0).unwrap();
+        assert_eq!(noon + tenor("1M"),
+                   Utc.with_ymd_and_hms(2017, 2, 28, 12, 0, 0).unwrap());
+
+        assert_eq!(2017.add_tenor(&tenor("2Y"), false), Some(2019));
+        assert_eq!(2017.add_tenor(&tenor("24M"), false), Some(2019));
+        assert_eq!(2017.add_tenor(&tenor("6M"), false), None);
+
+        assert_eq!(YearMonth::new(2017, 11).unwrap() + tenor("3M"),
+                   YearMonth::new(2018, 2).unwrap());
+        assert_eq!(YearMonth::new(2017, 11).unwrap().add_tenor(&tenor("1W"), false),
+                   None);
+        assert_eq!(YearQuarter::new(2017, 4).unwrap() - tenor("1Y"),
+                   YearQuarter::new(2016, 4).unwrap());
+        assert_eq!(YearQuarter::new(2017, 4).unwrap().add_tenor(&tenor("1M"), false),
+                   None);
+        assert_eq!(YearMonth::new(2017, 11).unwrap().add_tenor(&tenor("300000Y"), false),
+                   None);
+        assert_eq!(YearQuarter::new(2017, 4).unwrap().add_tenor(&tenor("-300000Y"), false),
+                   None);
+        assert_eq!(2017.add_tenor(&tenor("200000000Y"), false), None);
+    }
+
+    #[test]
+    fn from_tenors() {
+        let anchor = date(2017, 10, 17);
+        let curve = TimeSeries::from_tenors(anchor,
+                                            &[(tenor("1Y"), 0.03),
+                                              (tenor("3M"), 0.01),
+                                              (tenor("6M"), 0.02)])
+            .unwrap();
+        assert_eq!(curve.iter().map(|tv| tv.time).collect::<Vec<_>>(),
+                   vec![date(2018, 1, 17), date(2018, 4, 17), date(2018, 10, 17)]);
+        assert_eq!(curve[0].value, 0.01);
+        assert!(TimeSeries::from_tenors(2017, &[(tenor("3M"), 0.01)]).is_none());
+    }
+}
@@ -133,91 +133,107 @@ impl<T> TimeComparable for TimeValue<T>
 #[cfg(test)]
 mod tests {
     use DateTimeValue;
+    use DateValue;
     use YearValue;
     use super::*;
     mod time_comparable_time_value_t {
         use super::*;
 
         #[test]
-        fn is_same_time() -> () {
+        fn is_same_time() {
             // custom <test fn time_comparable_time_value_t_is_same_time>
-            assert_eq!(tv1().is_same_time(&tv1()), true);
-            assert_eq!(tv1().is_same_time(&tv2()), false);
+            assert!(tv1().is_same_time(&tv1()));
+            assert!(!tv1().is_same_time(&tv2()));
 
             let dtv1 = dtv1();
-            assert_eq!(dtv1.is_same_time(&dtv1), true);
+            assert!(dtv1.is_same_time(&dtv1));
             let dtv2 = dtv2();
-            assert_eq!(dtv1.is_same_time(&dtv2), false);
-            assert_eq!(dtv2.is_same_time(&dtv1), false);
-            assert_eq!(dtv2.is_same_time(&dtv2), true);
+            assert!(!dtv1.is_same_time(&dtv2));
+            assert!(!dtv2.is_same_time(&dtv1));
+            assert!(dtv2.is_same_time(&dtv2));
+
+            assert!(dv1().is_same_time(&dv1()));
+            assert!(!dv1().is_same_time(&dv2()));
 
             // end <test fn time_comparable_time_value_t_is_same_time>
         }
 
         #[test]
-        fn is_before() -> () {
+        fn is_before() {
             // custom <test fn time_comparable_time_value_t_is_before>
-            assert_eq!(tv1().is_before(&tv1()), false);
-            assert_eq!(tv1().is_before(&tv2()), true);
+            assert!(!tv1().is_before(&tv1()));
+            assert!(tv1().is_before(&tv2()));
 
             let dtv1 = dtv1();
-            assert_eq!(dtv1.is_before(&dtv1), false);
+            assert!(!dtv1.is_before(&dtv1));
             let dtv2 = dtv2();
-            assert_eq!(dtv1.is_before(&dtv2), true);
-            assert_eq!(dtv2.is_before(&dtv1), false);
-            assert_eq!(dtv2.is_before(&dtv2), false);
+            assert!(dtv1.is_before(&dtv2));
+            assert!(!dtv2.is_before(&dtv1));
+            assert!(!dtv2.is_before(&dtv2));
+
+            assert!(dv1().is_before(&dv2()));
+            assert!(!dv2().is_before(&dv1()));
 
             // end <test fn time_comparable_time_value_t_is_before>
         }
 
         #[test]
-        fn is_on_or_before() -> () {
+        fn is_on_or_before() {
             // custom <test fn time_comparable_time_value_t_is_on_or_before>
 
-            assert_eq!(tv1().is_on_or_before(&tv1()), true);
-            assert_eq!(tv1().is_on_or_before(&tv2()), true);
-            assert_eq!(tv2().is_on_or_before(&tv1()), false);
+            assert!(tv1().is_on_or_before(&tv1()));
+            assert!(tv1().is_on_or_before(&tv2()));
+            assert!(!tv2().is_on_or_before(&tv1()));
 
             let dtv1 = dtv1();
-            assert_eq!(dtv1.is_on_or_before(&dtv1), true);
+            assert!(dtv1.is_on_or_before(&dtv1));
             let dtv2 = dtv2();
-            assert_eq!(dtv1.is_on_or_before(&dtv2), true);
-            assert_eq!(dtv2.is_on_or_before(&dtv1), false);
-            assert_eq!(dtv2.is_on_or_before(&dtv2), true);
+            assert!(dtv1.is_on_or_before(&dtv2));
+            assert!(!dtv2.is_on_or_before(&dtv1));
+            assert!(dtv2.is_on_or_before(&dtv2));
+
+            assert!(dv1().is_on_or_before(&dv1()));
+            assert!(!dv2().is_on_or_before(&dv1()));
 
             // end <test fn time_comparable_time_value_t_is_on_or_before>
         }
 
         #[test]
-        fn is_after() -> () {
+        fn is_after() {
             // custom <test fn time_comparable_time_value_t_is_after>
-            assert_eq!(tv1().is_after(&tv1()), false);
-            assert_eq!(tv1().is_after(&tv2()), false);
-            assert_eq!(tv2().is_after(&tv1()), true);
+            assert!(!tv1().is_after(&tv1()));
+            assert!(!tv1().is_after(&tv2()));
+            assert!(tv2().is_after(&tv1()));
 
             let dtv1 = dtv1();
-            assert_eq!(dtv1.is_after(&dtv1), false);
+            assert!(!dtv1.is_after(&dtv1));
             let dtv2 = dtv2();
-            assert_eq!(dtv1.is_after(&dtv2), false);
-            assert_eq!(dtv2.is_after(&dtv1), true);
-            assert_eq!(dtv2.is_after(&dtv2), false);
+            assert!(!dtv1.is_after(&dtv2));
+            assert!(dtv2.is_after(&dtv1));
+            assert!(!dtv2.is_after(&dtv2));
+
+            assert!(dv2().is_after(&dv1()));
+            assert!(!dv1().is_after(&dv1()));
             // end <test fn time_comparable_time_value_t_is_after>
         }
 
         #[test]
-        fn is_on_or_after() -> () {
+        fn is_on_or_after() {
             // custom <test fn time_comparable_time_value_t_is_on_or_after>
 
-            assert_eq!(tv1().is_on_or_after(&tv1()), true);
-            assert_eq!(tv1().is_on_or_after(&tv2()), false);
-            assert_eq!(tv2().is_on_or_after(&tv1()), true);
+            assert!(tv1().is_on_or_after(&tv1()));
+            assert!(!tv1().is_on_or_after(&tv2()));
+            assert!(tv2().is_on_or_after(&tv1()));
 
             let dtv1 = dtv1();
-            assert_eq!(dtv1.is_on_or_after(&dtv1), true);
+            assert!(dtv1.is_on_or_after(&dtv1));
             let dtv2 = dtv2();
-            assert_eq!(dtv1.is_on_or_after(&dtv2), false);
-            assert_eq!(dtv2.is_on_or_after(&dtv1), true);
-            assert_eq!(dtv2.is_on_or_after(&dtv2), true);
+            assert!(!dtv1.is_on_or_after(&dtv2));
+            assert!(dtv2.is_on_or_after(&dtv1));
+            assert!(dtv2.is_on_or_after(&dtv2));
+
+            assert!(dv1().is_on_or_after(&dv1()));
+            assert!(!dv1().is_on_or_after(&dv2()));
             // end <test fn time_comparable_time_value_t_is_on_or_after>
         }
 
@@ -225,35 +241,45 @@ mod tests {
         use chrono::prelude::*;
 
         fn tv1() -> YearValue {
-            let tv1 = YearValue {
+            YearValue {
                 time: 1,
-                value: 3.14,
-            };
-            tv1
+                value: 2.5,
+            }
         }
 
         fn tv2() -> YearValue {
-            let tv2 = YearValue {
+            YearValue {
                 time: 2,
-                value: 3.14,
-            };
-            tv2
+                value: 2.5,
+            }
         }
 
         fn dtv1() -> DateTimeValue {
-            let dtv1 = DateTimeValue {
+            DateTimeValue {
                 time: Utc::now(),
-                value: 3.14,
-            };
-            dtv1
+                value: 2.5,
+            }
         }
 
         fn dtv2() -> DateTimeValue {
-            let dtv2 = DateTimeValue {
+            DateTimeValue {
                 time: Utc::now(),
-                value: 3.14,
-            };
-            dtv2
+                value: 2.5,
+            }
+        }
+
+        fn dv1() -> DateValue {
+            DateValue {
+                time: NaiveDate::from_ymd_opt(2017, 10, 16).unwrap(),
+                value: 2.5,
+            }
+        }
+
+        fn dv2() -> DateValue {
+            DateValue {
+                time: NaiveDate::from_ymd_opt(2017, 10, 17).unwrap(),
+                value: 2.5,
+            }
         }
 
         // end <module ModuleCodeBlock.moduleBottom>
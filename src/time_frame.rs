@@ -0,0 +1,458 @@
+//! Support for many values per time, such as the balances of several accounts, held as one
+//! two-dimensional array
+
+// --- module use statements ---
+
+use Join;
+use Range;
+use Time;
+use TimeInterval;
+use TimeSearchable;
+use TimeSeries;
+use TimeSeriesError;
+use TimeStep;
+use TimeValue;
+use join;
+use ndarray::Array1;
+use ndarray::Array2;
+use ndarray::ArrayView1;
+use ndarray::Axis;
+use std::error::Error;
+use std::fmt;
+use time_searchable::search_interval;
+
+// --- module enum definitions ---
+
+/// Reasons data cannot form a `TimeFrame`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TimeFrameError {
+    /// The times are not in strictly increasing order
+    Times(TimeSeriesError),
+    /// The values do not have a row per time and a column per name
+    Shape {
+        /// Rows expected, one per time
+        rows: usize,
+        /// Columns expected, one per name
+        columns: usize,
+    },
+    /// The name is given to more than one column
+    DuplicateColumn(String),
+}
+
+// --- module struct definitinos ---
+
+/// Named columns of values sharing one time axis, with a row per time
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeFrame<T> {
+    /// Times in strictly increasing order
+    times: Vec<T>,
+    /// Name of each column
+    names: Vec<String>,
+    /// Values with a row per time and a column per name
+    values: Array2<f64>,
+}
+
+// --- module impl definitions ---
+
+impl<T> TimeFrame<T>
+    where T: Time<T = T> + Ord
+{
+    /// Create a `TimeFrame`
+    ///
+    ///  * `times` - Times in strictly increasing order
+    ///  * `names` - Distinct name of each column
+    ///  * `values` - Values with a row per time and a column per name
+    ///  * return - The frame, or an error describing the inconsistent data
+    ///
+    pub fn new(times: Vec<T>,
+               names: Vec<String>,
+               values: Array2<f64>)
+               -> Result<TimeFrame<T>, TimeFrameError> {
+        // custom <fn time_frame_new>
+
+        if values.rows() != times.len() || values.cols() != names.len() {
+            return Err(TimeFrameError::Shape {
+                           rows: times.len(),
+                           columns: names.len(),
+                       });
+        }
+        for index in 1..times.len() {
+            if times[index] < times[index - 1] {
+                return Err(TimeFrameError::Times(TimeSeriesError::Unsorted { index }));
+            }
+            if times[index] == times[index - 1] {
+                return Err(TimeFrameError::Times(TimeSeriesError::DuplicateTime { index }));
+            }
+        }
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(TimeFrameError::DuplicateColumn(name.clone()));
+            }
+        }
+        Ok(TimeFrame {
+               times,
+               names,
+               values,
+           })
+
+        // end <fn time_frame_new>
+    }
+
+    /// Times of the frame in increasing order
+    pub fn times(&self) -> &[T] {
+        &self.times
+    }
+
+    /// Names of the columns
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Values with a row per time and a column per name
+    pub fn values(&self) -> &Array2<f64> {
+        &self.values
+    }
+
+    /// Number of times in the frame
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// True if the frame has no times
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// The values of the column named `name`
+    pub fn column(&self, name: &str) -> Option<ArrayView1<'_, f64>> {
+        self.position(name).map(|i| self.values.column(i))
+    }
+
+    /// A frame of the columns named in `names`, in that order
+    ///
+    ///  * `names` - Columns to keep
+    ///  * return - The frame, or `None` if a name is not a column or is repeated
+    ///
+    pub fn select(&self, names: &[&str]) -> Option<TimeFrame<T>> {
+        // custom <fn time_frame_select>
+        let indices = names.iter().map(|name| self.position(name)).collect::<Option<Vec<_>>>()?;
+        TimeFrame::new(self.times.clone(),
+                       names.iter().map(|name| name.to_string()).collect(),
+                       self.values.select(Axis(1), &indices))
+            .ok()
+        // end <fn time_frame_select>
+    }
+
+    /// A frame of the rows in `rows`, as found by the `TimeSearchable` methods
+    ///
+    /// A range ending before it starts gives no rows.
+    pub fn rows(&self, rows: Range<usize>) -> TimeFrame<T> {
+        let rows = rows.start..rows.end.max(rows.start);
+        TimeFrame {
+            times: self.times[rows.clone()].to_vec(),
+            names: self.names.clone(),
+            values: self.values.slice(s![rows.start as isize..rows.end as isize, ..]).to_owned(),
+        }
+    }
+
+    /// Adds a column, or replaces the column of the same name
+    ///
+    ///  * `name` - Name of the column
+    ///  * `values` - Value of the column at each time
+    ///  * return - An error if `values` does not hold a value for each time
+    ///
+    pub fn set_column(&mut self, name: &str, values: Array1<f64>) -> Result<(), TimeFrameError> {
+        // custom <fn time_frame_set_column>
+
+        if values.len() != self.len() {
+            return Err(TimeFrameError::Shape {
+                           rows: self.len(),
+                           columns: self.names.len(),
+                       });
+        }
+        match self.position(name) {
+            Some(i) => self.values.column_mut(i).assign(&values),
+            None => {
+                let column = values.into_shape((self.len(), 1)).expect("one column");
+                self.values = ::ndarray::stack(Axis(1), &[self.values.view(), column.view()])
+                    .expect("rows match");
+                self.names.push(name.to_string());
+            }
+        }
+        Ok(())
+
+        // end <fn time_frame_set_column>
+    }
+
+    /// Combines two columns value by value, such as income less expense
+    ///
+    ///  * `left` - Name of the column supplying the left-hand values
+    ///  * `right` - Name of the column supplying the right-hand values
+    ///  * `f` - Combines a left and right value
+    ///  * return - The combined values, or `None` if a name is not a column
+    ///
+    pub fn combine<F>(&self, left: &str, right: &str, f: F) -> Option<Array1<f64>>
+        where F: Fn(f64, f64) -> f64
+    {
+        // custom <fn time_frame_combine>
+        let (left, right) = (self.column(left)?, self.column(right)?);
+        Some(left.iter().zip(right.iter()).map(|(&a, &b)| f(a, b)).collect())
+        // end <fn time_frame_combine>
+    }
+
+    /// Replaces each value of the column named `name` with the result of `f`
+    ///
+    ///  * `name` - Column to change
+    ///  * `f` - Function of a value
+    ///  * return - True if `name` is a column
+    ///
+    pub fn map_column<F>(&mut self, name: &str, f: F) -> bool
+        where F: Fn(f64) -> f64
+    {
+        match self.position(name) {
+            Some(i) => {
+                self.values.column_mut(i).mapv_inplace(f);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sum across the columns at each time, such as the total of several accounts
+    pub fn row_sums(&self) -> TimeSeries<T> {
+        TimeSeries::from_sorted(self.times
+                                    .iter()
+                                    .zip(self.values.sum_axis(Axis(1)).iter())
+                                    .map(|(&time, &value)| TimeValue { time, value })
+                                    .collect())
+    }
+
+    /// The column named `name` as a series, leaving out `NaN` values
+    pub fn to_series(&self, name: &str) -> Option<TimeSeries<T>> {
+        // custom <fn time_frame_to_series>
+        let column = self.column(name)?;
+        Some(TimeSeries::from_sorted(self.times
+                                         .iter()
+                                         .zip(column.iter())
+                                         .filter(|&(_, value)| !value.is_nan())
+                                         .map(|(&time, &value)| TimeValue { time, value })
+                                         .collect()))
+        // end <fn time_frame_to_series>
+    }
+
+    /// Each column as a named series, leaving out `NaN` values
+    pub fn to_series_all(&self) -> Vec<(String, TimeSeries<T>)> {
+        self.names
+            .iter()
+            .map(|name| (name.clone(), self.to_series(name).expect("name is a column")))
+            .collect()
+    }
+
+    // custom <impl TimeFrame>
+
+    /// Position of the column named `name`
+    fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Index of `time`, or the index it would be inserted at
+    fn search(&self, time: &T) -> Result<usize, usize> {
+        self.times.binary_search(time)
+    }
+
+    // end <impl TimeFrame>
+}
+
+impl<T> TimeFrame<T>
+    where T: TimeStep + Ord
+{
+    /// Create a `TimeFrame` from named series, with a row for every time in any series
+    ///
+    ///  * `series` - Name and series of each column
+    ///  * return - The frame, with `NaN` where a series has no value at a time, or an error if
+    ///    a name is repeated
+    ///
+    pub fn from_series(series: &[(&str, &TimeSeries<T>)]) -> Result<TimeFrame<T>, TimeFrameError> {
+        // custom <fn time_frame_from_series>
+
+        let joined = join(&series.iter().map(|&(_, s)| s).collect::<Vec<_>>(), Join::Outer)
+            .expect("outer joins move no times");
+        let mut values = Array2::from_elem((joined.len(), series.len()), f64::NAN);
+        for (j, column) in joined.columns.iter().enumerate() {
+            for (i, value) in column.iter().enumerate() {
+                if let Some(value) = *value {
+                    values[[i, j]] = value;
+                }
+            }
+        }
+        TimeFrame::new(joined.times,
+                       series.iter().map(|&(name, _)| name.to_string()).collect(),
+                       values)
+
+        // end <fn time_frame_from_series>
+    }
+}
+
+/// Provide search into `TimeFrame` rows by time
+impl<T> TimeSearchable for TimeFrame<T>
+    where T: Time<T = T> + Ord
+{
+    type T = T;
+    type TV = TimeValue<T>;
+
+    /// Finds range of rows whose time is `after` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `after`
+    ///  * return - Returns range to resulting rows
+    ///
+    fn after(&self, time: T) -> Range<usize> {
+        match self.search(&time) {
+            Ok(i) => (i + 1)..self.len(),
+            Err(i) => i..self.len(),
+        }
+    }
+
+    /// Finds range of rows whose time is `before` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `before`
+    ///  * return - Returns range to resulting rows
+    ///
+    fn before(&self, time: T) -> Range<usize> {
+        match self.search(&time) {
+            Ok(i) | Err(i) => 0..i,
+        }
+    }
+
+    /// Finds range of rows whose time is `on_or_after` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `onOrAfter`
+    ///  * return - Returns range to resulting rows
+    ///
+    fn on_or_after(&self, time: T) -> Range<usize> {
+        match self.search(&time) {
+            Ok(i) | Err(i) => i..self.len(),
+        }
+    }
+
+    /// Finds range of rows whose time is `on_or_before` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `onOrBefore`
+    ///  * return - Returns range to resulting rows
+    ///
+    fn on_or_before(&self, time: T) -> Range<usize> {
+        match self.search(&time) {
+            Ok(i) => 0..(i + 1),
+            Err(i) => 0..i,
+        }
+    }
+
+    /// Find range of rows in `TimeFrame`
+    ///
+    ///  * `range` - Times to find, including `range.start` but not `range.end`
+    ///  * return - Returns range of rows based on `range`
+    ///
+    fn in_range(&self, range: Range<T>) -> Range<usize> {
+        self.in_interval(&range.into())
+    }
+
+    /// Find range of rows in `TimeFrame` whose times are in `interval`
+    ///
+    ///  * `interval` - Interval whose bounds decide if its end times are included
+    ///  * return - Returns range of rows based on `interval`
+    ///
+    fn in_interval(&self, interval: &TimeInterval<T>) -> Range<usize> {
+        search_interval(self, interval)
+    }
+}
+
+impl fmt::Display for TimeFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TimeFrameError::Times(ref error) => write!(f, "{}", error),
+            TimeFrameError::Shape { rows, columns } => {
+                write!(f, "values must have {} rows and {} columns", rows, columns)
+            }
+            TimeFrameError::DuplicateColumn(ref name) => {
+                write!(f, "column `{}` appears more than once", name)
+            }
+        }
+    }
+}
+
+impl Error for TimeFrameError {}
+
+impl From<TimeSeriesError> for TimeFrameError {
+    fn from(error: TimeSeriesError) -> TimeFrameError {
+        TimeFrameError::Times(error)
+    }
+}
+
+/// Test module for time_frame module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Year;
+
+    fn series(points: &[(Year, f64)]) -> TimeSeries<Year> {
+        points.iter().map(|&(time, value)| TimeValue { time, value }).collect()
+    }
+
+    fn accounts() -> TimeFrame<Year> {
+        TimeFrame::from_series(&[("cash", &series(&[(2017, 1.0), (2018, 2.0), (2019, 3.0)])),
+                                 ("bonds", &series(&[(2018, 10.0), (2019, 20.0), (2020, 30.0)]))])
+            .unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let values = Array2::zeros((2, 1));
+        assert!(TimeFrame::new(vec![1, 2], vec!["a".to_string()], values.clone()).is_ok());
+        assert_eq!(TimeFrame::new(vec![2, 1], vec!["a".to_string()], values.clone()),
+                   Err(TimeFrameError::Times(TimeSeriesError::Unsorted { index: 1 })));
+        assert_eq!(TimeFrame::new(vec![1], vec!["a".to_string()], values.clone()),
+                   Err(TimeFrameError::Shape { rows: 1, columns: 1 }));
+        assert_eq!(TimeFrame::new(vec![1, 2], vec!["a".to_string(), "a".to_string()],
+                                  Array2::zeros((2, 2))),
+                   Err(TimeFrameError::DuplicateColumn("a".to_string())));
+    }
+
+    #[test]
+    fn series_round_trip() {
+        let frame = accounts();
+        assert_eq!(frame.times(), &[2017, 2018, 2019, 2020]);
+        assert!(frame.column("bonds").unwrap()[0].is_nan());
+        let columns = frame.to_series_all();
+        assert_eq!(columns[0].0, "cash");
+        assert_eq!(columns[1].1.clone().into_vec(),
+                   series(&[(2018, 10.0), (2019, 20.0), (2020, 30.0)]).into_vec());
+        assert!(frame.to_series("stocks").is_none());
+    }
+
+    #[test]
+    fn select_and_rows() {
+        let frame = accounts();
+        let bonds = frame.select(&["bonds"]).unwrap();
+        assert_eq!(bonds.names(), &["bonds".to_string()]);
+        assert!(frame.select(&["bonds", "bonds"]).is_none());
+
+        let middle = frame.rows(frame.in_range(2018..2020));
+        assert_eq!(middle.times(), &[2018, 2019]);
+        assert_eq!(middle.values()[[1, 1]], 20.0);
+        assert!(frame.rows(Range { start: 3, end: 1 }).is_empty());
+        assert_eq!(frame.on_or_before(2018), 0..2);
+        assert_eq!(frame.after(2018), 2..4);
+    }
+
+    #[test]
+    fn column_arithmetic() {
+        let mut frame = accounts().rows(1..3);
+        let total = frame.combine("cash", "bonds", |a, b| a + b).unwrap();
+        assert_eq!(total.to_vec(), vec![12.0, 23.0]);
+        frame.set_column("total", total).unwrap();
+        assert!(frame.map_column("cash", |v| v * 2.0));
+        assert_eq!(frame.column("cash").unwrap().to_vec(), vec![4.0, 6.0]);
+        assert_eq!(frame.row_sums().iter().map(|tv| tv.value).collect::<Vec<_>>(),
+                   vec![26.0, 49.0]);
+        assert!(frame.set_column("short", Array1::zeros(1)).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Support for intervals of time, such as employment periods, loan terms or accrual periods
+
+// --- module use statements ---
+
+use DayCount;
+use Range;
+use Time;
+
+// --- module enum definitions ---
+
+/// Whether an end of a `TimeInterval` includes its time
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum IntervalBound {
+    /// The interval excludes the time at this end
+    Open,
+    /// The interval includes the time at this end
+    Closed,
+}
+
+// --- module struct definitinos ---
+
+/// An interval of time between `start` and `end`, each of which is open or closed
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct TimeInterval<T> {
+    /// Earliest time of the interval
+    pub start: T,
+    /// Whether `start` is in the interval
+    pub start_bound: IntervalBound,
+    /// Latest time of the interval
+    pub end: T,
+    /// Whether `end` is in the interval
+    pub end_bound: IntervalBound,
+}
+
+// --- module impl definitions ---
+
+impl<T> TimeInterval<T>
+    where T: Time<T = T> + Ord
+{
+    /// Create a `TimeInterval`
+    ///
+    ///  * `start` - Earliest time of the interval
+    ///  * `start_bound` - Whether `start` is in the interval
+    ///  * `end` - Latest time of the interval
+    ///  * `end_bound` - Whether `end` is in the interval
+    ///  * return - The interval
+    ///
+    pub fn new(start: T,
+               start_bound: IntervalBound,
+               end: T,
+               end_bound: IntervalBound)
+               -> TimeInterval<T> {
+        TimeInterval {
+            start,
+            start_bound,
+            end,
+            end_bound,
+        }
+    }
+
+    /// The interval [`start`, `end`], including both ends
+    pub fn closed(start: T, end: T) -> TimeInterval<T> {
+        TimeInterval::new(start, IntervalBound::Closed, end, IntervalBound::Closed)
+    }
+
+    /// The interval (`start`, `end`), excluding both ends
+    pub fn open(start: T, end: T) -> TimeInterval<T> {
+        TimeInterval::new(start, IntervalBound::Open, end, IntervalBound::Open)
+    }
+
+    /// The interval [`start`, `end`), including `start` but not `end`
+    pub fn closed_open(start: T, end: T) -> TimeInterval<T> {
+        TimeInterval::new(start, IntervalBound::Closed, end, IntervalBound::Open)
+    }
+
+    /// The interval (`start`, `end`], including `end` but not `start`
+    pub fn open_closed(start: T, end: T) -> TimeInterval<T> {
+        TimeInterval::new(start, IntervalBound::Open, end, IntervalBound::Closed)
+    }
+
+    /// Determines if the interval contains no times
+    pub fn is_empty(&self) -> bool {
+        // custom <fn time_interval_is_empty>
+        self.start > self.end ||
+        (self.start == self.end &&
+         (self.start_bound == IntervalBound::Open || self.end_bound == IntervalBound::Open))
+        // end <fn time_interval_is_empty>
+    }
+
+    /// Determines if `time` is in the interval
+    ///
+    ///  * `time` - Time to check
+    ///  * return - True if `time` is in the interval
+    ///
+    pub fn contains(&self, time: &T) -> bool {
+        // custom <fn time_interval_contains>
+
+        let after_start = match self.start_bound {
+            IntervalBound::Open => *time > self.start,
+            IntervalBound::Closed => *time >= self.start,
+        };
+        let before_end = match self.end_bound {
+            IntervalBound::Open => *time < self.end,
+            IntervalBound::Closed => *time <= self.end,
+        };
+        after_start && before_end
+
+        // end <fn time_interval_contains>
+    }
+
+    /// Determines if the interval shares any time with `other`
+    ///
+    ///  * `other` - Interval to compare with
+    ///  * return - True if some time is in both intervals
+    ///
+    pub fn overlaps(&self, other: &TimeInterval<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The times in both the interval and `other`
+    ///
+    ///  * `other` - Interval to intersect with
+    ///  * return - The intersection, or `None` if the intervals do not overlap
+    ///
+    pub fn intersection(&self, other: &TimeInterval<T>) -> Option<TimeInterval<T>> {
+        // custom <fn time_interval_intersection>
+
+        let (start, start_bound) = later_start(self, other);
+        let (end, end_bound) = earlier_end(self, other);
+        let result = TimeInterval::new(start, start_bound, end, end_bound);
+        if result.is_empty() { None } else { Some(result) }
+
+        // end <fn time_interval_intersection>
+    }
+
+    /// The times in either the interval or `other`
+    ///
+    ///  * `other` - Interval to join with
+    ///  * return - The union, or `None` if a gap separates the intervals
+    ///
+    pub fn union(&self, other: &TimeInterval<T>) -> Option<TimeInterval<T>> {
+        // custom <fn time_interval_union>
+
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.gap(other).is_some() {
+            return None;
+        }
+
+        let (start, start_bound) = if (self.start, start_rank(self.start_bound)) <=
+                                      (other.start, start_rank(other.start_bound)) {
+            (self.start, self.start_bound)
+        } else {
+            (other.start, other.start_bound)
+        };
+        let (end, end_bound) = if (self.end, end_rank(self.end_bound)) >=
+                                  (other.end, end_rank(other.end_bound)) {
+            (self.end, self.end_bound)
+        } else {
+            (other.end, other.end_bound)
+        };
+        Some(TimeInterval::new(start, start_bound, end, end_bound))
+
+        // end <fn time_interval_union>
+    }
+
+    /// The times strictly between the interval and `other`
+    ///
+    ///  * `other` - Interval to compare with
+    ///  * return - The gap separating the intervals, or `None` if they overlap or meet
+    ///
+    pub fn gap(&self, other: &TimeInterval<T>) -> Option<TimeInterval<T>> {
+        // custom <fn time_interval_gap>
+
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let result = TimeInterval::new(first.end,
+                                       flip(first.end_bound),
+                                       second.start,
+                                       flip(second.start_bound));
+        if first.end > second.start || result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+
+        // end <fn time_interval_gap>
+    }
+
+    /// Years from `start` until `end`
+    pub fn duration_years(&self) -> f64 {
+        self.start.years_until(&self.end)
+    }
+
+    /// Years from `start` until `end` measured with `day_count`
+    ///
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Years from `start` until `end`
+    ///
+    pub fn duration_years_with<DC>(&self, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        self.start.years_until_with(&self.end, day_count)
+    }
+
+    // custom <impl TimeInterval>
+    // end <impl TimeInterval>
+}
+
+/// A `Range` is the half-open interval [`start`, `end`)
+impl<T> From<Range<T>> for TimeInterval<T>
+    where T: Time<T = T> + Ord
+{
+    fn from(range: Range<T>) -> TimeInterval<T> {
+        TimeInterval::closed_open(range.start, range.end)
+    }
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// The other kind of bound
+fn flip(bound: IntervalBound) -> IntervalBound {
+    match bound {
+        IntervalBound::Open => IntervalBound::Closed,
+        IntervalBound::Closed => IntervalBound::Open,
+    }
+}
+
+/// Orders starts at the same time, an open start beginning after a closed one
+fn start_rank(bound: IntervalBound) -> u8 {
+    match bound {
+        IntervalBound::Closed => 0,
+        IntervalBound::Open => 1,
+    }
+}
+
+/// Orders ends at the same time, an open end finishing before a closed one
+fn end_rank(bound: IntervalBound) -> u8 {
+    match bound {
+        IntervalBound::Open => 0,
+        IntervalBound::Closed => 1,
+    }
+}
+
+/// The later start of two intervals
+fn later_start<T>(a: &TimeInterval<T>, b: &TimeInterval<T>) -> (T, IntervalBound)
+    where T: Ord + Copy
+{
+    if (a.start, start_rank(a.start_bound)) >= (b.start, start_rank(b.start_bound)) {
+        (a.start, a.start_bound)
+    } else {
+        (b.start, b.start_bound)
+    }
+}
+
+/// The earlier end of two intervals
+fn earlier_end<T>(a: &TimeInterval<T>, b: &TimeInterval<T>) -> (T, IntervalBound)
+    where T: Ord + Copy
+{
+    if (a.end, end_rank(a.end_bound)) <= (b.end, end_rank(b.end_bound)) {
+        (a.end, a.end_bound)
+    } else {
+        (b.end, b.end_bound)
+    }
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for time_interval module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TimeSearchable;
+    use TimeSeries;
+    use TimeValue;
+    use Year;
+
+    fn series() -> TimeSeries<Year> {
+        (1..6).map(|t| TimeValue { time: t, value: t as f64 }).collect()
+    }
+
+    #[test]
+    fn contains() {
+        assert!(TimeInterval::closed(1, 3).contains(&3));
+        assert!(!TimeInterval::closed_open(1, 3).contains(&3));
+        assert!(!TimeInterval::open_closed(1, 3).contains(&1));
+        assert!(TimeInterval::open(1, 3).contains(&2));
+        assert!(TimeInterval::open(1, 1).is_empty());
+        assert!(!TimeInterval::closed(1, 1).is_empty());
+        assert!(TimeInterval::closed(2, 1).is_empty());
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(TimeInterval::closed(1, 3).intersection(&TimeInterval::open(2, 5)),
+                   Some(TimeInterval::open_closed(2, 3)));
+        assert_eq!(TimeInterval::closed(1, 3).intersection(&TimeInterval::closed(3, 5)),
+                   Some(TimeInterval::closed(3, 3)));
+        assert_eq!(TimeInterval::closed_open(1, 3).intersection(&TimeInterval::closed(3, 5)),
+                   None);
+        assert!(TimeInterval::closed(1, 3).overlaps(&TimeInterval::closed(3, 5)));
+        assert!(!TimeInterval::closed_open(1, 3).overlaps(&TimeInterval::closed(3, 5)));
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!(TimeInterval::closed_open(1, 3).union(&TimeInterval::closed(3, 5)),
+                   Some(TimeInterval::closed(1, 5)));
+        assert_eq!(TimeInterval::open(1, 3).union(&TimeInterval::closed(1, 2)),
+                   Some(TimeInterval::closed_open(1, 3)));
+        assert_eq!(TimeInterval::closed_open(1, 3).union(&TimeInterval::open(3, 5)),
+                   None);
+        assert_eq!(TimeInterval::closed(1, 2).union(&TimeInterval::closed(4, 5)), None);
+    }
+
+    #[test]
+    fn gap() {
+        assert_eq!(TimeInterval::closed(1, 2).gap(&TimeInterval::closed(4, 5)),
+                   Some(TimeInterval::open(2, 4)));
+        assert_eq!(TimeInterval::closed(4, 5).gap(&TimeInterval::closed_open(1, 2)),
+                   Some(TimeInterval::closed_open(2, 4)));
+        assert_eq!(TimeInterval::closed_open(1, 3).gap(&TimeInterval::open(3, 5)),
+                   Some(TimeInterval::closed(3, 3)));
+        assert_eq!(TimeInterval::closed_open(1, 3).gap(&TimeInterval::closed(3, 5)), None);
+        assert_eq!(TimeInterval::closed(1, 3).gap(&TimeInterval::closed(2, 5)), None);
+    }
+
+    #[test]
+    fn duration_years() {
+        assert_relative_eq!(TimeInterval::closed(2017, 2020).duration_years(), 3.0);
+        assert_eq!(TimeInterval::from(1..3), TimeInterval::closed_open(1, 3));
+    }
+
+    #[test]
+    fn in_interval() {
+        let series = series();
+        assert_eq!(series.in_interval(&TimeInterval::closed(2, 4)), 1..4);
+        assert_eq!(series.in_interval(&TimeInterval::open(2, 4)), 2..3);
+        assert_eq!(series.in_interval(&TimeInterval::open_closed(0, 1)), 0..1);
+        assert_eq!(series.in_interval(&TimeInterval::open(2, 2)), 2..2);
+        assert_eq!(series.in_interval(&(2..4).into()), series.in_range(2..4));
+    }
+}
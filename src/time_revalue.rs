@@ -2,8 +2,10 @@
 
 // --- module use statements ---
 
+use DayCount;
 use Time;
 use TimeValue;
+use std::ops::Mul;
 
 // --- module trait definitions ---
 
@@ -18,7 +20,18 @@ pub trait TimeRevalue {
     ///  * `cc_rate` - Countinously compounded rate for revalue
     ///  * return - Returns original value revalued to `target_time` with `cc_rate`
     ///
-    fn revalue_on<'a>(&'a self, target_time: Self::T, cc_rate: f64) -> Self;
+    fn revalue_on(&self, target_time: Self::T, cc_rate: f64) -> Self;
+
+    /// Revalue `self` from it's time to `target_time` with rate `r`, measuring years with
+    /// `day_count`
+    ///
+    ///  * `target_time` - Time to revalue to
+    ///  * `cc_rate` - Countinously compounded rate for revalue
+    ///  * `day_count` - Convention used to measure the years of growth
+    ///  * return - Returns original value revalued to `target_time` with `cc_rate`
+    ///
+    fn revalue_on_with<DC>(&self, target_time: Self::T, cc_rate: f64, day_count: &DC) -> Self
+        where DC: DayCount + ?Sized;
 
     // custom <trait_time_revalue>
     // end <trait_time_revalue>
@@ -26,9 +39,11 @@ pub trait TimeRevalue {
 
 // --- module impl definitions ---
 
-/// Provide `TimeRevalue` implementation for `TimeValue<T>`
-impl<T> TimeRevalue for TimeValue<T>
-    where T: Time<T = T> + Ord
+/// Provide `TimeRevalue` implementation for `TimeValue<T, V>`, for values that scale by a
+/// growth factor
+impl<T, V> TimeRevalue for TimeValue<T, V>
+    where T: Time<T = T> + Ord,
+          V: Mul<f64, Output = V> + Copy
 {
     type T = T;
 
@@ -38,19 +53,40 @@ impl<T> TimeRevalue for TimeValue<T>
     ///  * `cc_rate` - Countinously compounded rate for revalue
     ///  * return - Returns original value revalued to `target_time` with `cc_rate`
     ///
-    fn revalue_on<'a>(&'a self, target_time: Self::T, cc_rate: f64) -> Self {
+    fn revalue_on(&self, target_time: Self::T, cc_rate: f64) -> Self {
         // custom <fn time_revalue_time_value_t_revalue_on>
 
         TimeValue {
             time: target_time,
-            value: self.value * (cc_rate * self.years_until(&target_time)).exp(),
+            value: self.value * (cc_rate * self.time.years_until(&target_time)).exp(),
         }
 
         // end <fn time_revalue_time_value_t_revalue_on>
     }
 
-    // custom <impl TimeRevalue for TimeValue<T>>
-    // end <impl TimeRevalue for TimeValue<T>>
+    /// Revalue `self` from it's time to `target_time` with rate `r`, measuring years with
+    /// `day_count`
+    ///
+    ///  * `target_time` - Time to revalue to
+    ///  * `cc_rate` - Countinously compounded rate for revalue
+    ///  * `day_count` - Convention used to measure the years of growth
+    ///  * return - Returns original value revalued to `target_time` with `cc_rate`
+    ///
+    fn revalue_on_with<DC>(&self, target_time: Self::T, cc_rate: f64, day_count: &DC) -> Self
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_revalue_time_value_t_revalue_on_with>
+
+        TimeValue {
+            time: target_time,
+            value: self.value * revalue_on(1.0, self.time, target_time, cc_rate, day_count),
+        }
+
+        // end <fn time_revalue_time_value_t_revalue_on_with>
+    }
+
+    // custom <impl TimeRevalue for TimeValue<T, V>>
+    // end <impl TimeRevalue for TimeValue<T, V>>
 }
 
 // --- module function definitions ---
@@ -61,14 +97,21 @@ impl<T> TimeRevalue for TimeValue<T>
 ///  * `source_time` - TODO: comment parm
 ///  * `target_time` - TODO: comment parm
 ///  * `cc_rate` - TODO: comment parm
+///  * `day_count` - Convention used to measure the years from `source_time` to `target_time`
 ///  * return - TODO: document return
 ///
-pub fn revalue_on<T>(source_value: f64, source_time: T, target_time: T, cc_rate: f64) -> f64
-    where T: Time<T = T>
+pub fn revalue_on<T, DC>(source_value: f64,
+                         source_time: T,
+                         target_time: T,
+                         cc_rate: f64,
+                         day_count: &DC)
+                         -> f64
+    where T: Time<T = T>,
+          DC: DayCount + ?Sized
 {
     // custom <fn revalue_on>
 
-    source_value * (cc_rate * source_time.years_until(&target_time)).exp()
+    source_value * (cc_rate * source_time.years_until_with(&target_time, day_count)).exp()
 
     // end <fn revalue_on>
 }
@@ -81,7 +124,7 @@ mod tests {
         use super::*;
 
         #[test]
-        fn revalue_on() -> () {
+        fn revalue_on() {
             // custom <test fn time_revalue_time_value_t_revalue_on>
 
             let tv1 = TimeValue {
@@ -95,6 +138,49 @@ mod tests {
             // end <test fn time_revalue_time_value_t_revalue_on>
         }
 
+        #[test]
+        fn revalue_on_with() {
+            // custom <test fn time_revalue_time_value_t_revalue_on_with>
+
+            use chrono::{TimeZone, Utc};
+            use {Act360, Act365Fixed, DateTimeValue};
+
+            let tv1 = DateTimeValue {
+                time: Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap(),
+                value: 1.0,
+            };
+            let target = Utc.with_ymd_and_hms(2017, 7, 1, 0, 0, 0).unwrap();
+
+            assert_relative_eq!(tv1.revalue_on_with(target, 0.03, &Act360).value,
+                                (0.03_f64 * 181.0 / 360.0).exp());
+            assert_relative_eq!(tv1.revalue_on_with(target, 0.03, &Act365Fixed).value,
+                                tv1.revalue_on(target, 0.03).value);
+            assert_relative_eq!(tv1.revalue_on_with(target, 0.03, &Act360)
+                                    .revalue_on_with(tv1.time, 0.03, &Act360)
+                                    .value,
+                                1.0);
+
+            // end <test fn time_revalue_time_value_t_revalue_on_with>
+        }
+
+        #[test]
+        fn revalue_on_date() {
+            // custom <test fn time_revalue_time_value_t_revalue_on_date>
+
+            use {Date, DateValue};
+
+            let tv1 = DateValue {
+                time: Date::from_ymd_opt(2017, 1, 1).unwrap(),
+                value: 1.0,
+            };
+
+            assert_relative_eq!(tv1.revalue_on(Date::from_ymd_opt(2018, 1, 1).unwrap(), 0.03)
+                                    .value,
+                                0.03_f64.exp());
+
+            // end <test fn time_revalue_time_value_t_revalue_on_date>
+        }
+
         // custom <module ModuleCodeBlock.moduleBottom>
         // end <module ModuleCodeBlock.moduleBottom>
     }
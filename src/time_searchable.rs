@@ -2,9 +2,12 @@
 
 // --- module use statements ---
 
+use IntervalBound;
 use Range;
 use Time;
+use TimeInterval;
 use TimeSeries;
+use TimeSeriesView;
 use TimeValue;
 
 // --- module trait definitions ---
@@ -50,7 +53,14 @@ pub trait TimeSearchable {
     ///  * `range` - TODO: comment parm
     ///  * return - Returns range in `TimeSeries` based on `range`
     ///
-    fn in_range<'a>(&'a self, range: Range<Self::T>) -> Range<usize>;
+    fn in_range(&self, range: Range<Self::T>) -> Range<usize>;
+
+    /// Find range in `TimeSeries` of the times in `interval`
+    ///
+    ///  * `interval` - Interval whose bounds decide if its end times are included
+    ///  * return - Returns range in `TimeSeries` based on `interval`
+    ///
+    fn in_interval(&self, interval: &TimeInterval<Self::T>) -> Range<usize>;
 
     // custom <trait_time_searchable>
     // end <trait_time_searchable>
@@ -59,11 +69,12 @@ pub trait TimeSearchable {
 // --- module impl definitions ---
 
 /// Provide ability to compare time component of`TimeValue`
-impl<T> TimeSearchable for TimeSeries<T>
-    where T: Time<T = T> + Ord
+impl<T, V> TimeSearchable for TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Copy
 {
     type T = T;
-    type TV = TimeValue<T>;
+    type TV = TimeValue<T, V>;
 
     /// Finds range of `Self` that is `after` `time`
     ///
@@ -72,13 +83,91 @@ impl<T> TimeSearchable for TimeSeries<T>
     ///
     fn after(&self, time: Self::T) -> Range<usize> {
         // custom <fn time_searchable_time_series_t_after>
+        self.view().after(time)
+        // end <fn time_searchable_time_series_t_after>
+    }
+
+    /// Finds range of `Self` that is `before` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `before`
+    ///  * return - Returns range to resulting items
+    ///
+    fn before(&self, time: Self::T) -> Range<usize> {
+        // custom <fn time_searchable_time_series_t_before>
+        self.view().before(time)
+        // end <fn time_searchable_time_series_t_before>
+    }
+
+    /// Finds range of `Self` that is `on_or_after` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `onOrAfter`
+    ///  * return - Returns range to resulting items
+    ///
+    fn on_or_after(&self, time: Self::T) -> Range<usize> {
+        // custom <fn time_searchable_time_series_t_on_or_after>
+        self.view().on_or_after(time)
+        // end <fn time_searchable_time_series_t_on_or_after>
+    }
+
+    /// Finds range of `Self` that is `on_or_before` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `onOrBefore`
+    ///  * return - Returns range to resulting items
+    ///
+    fn on_or_before(&self, time: Self::T) -> Range<usize> {
+        // custom <fn time_searchable_time_series_t_on_or_before>
+        self.view().on_or_before(time)
+        // end <fn time_searchable_time_series_t_on_or_before>
+    }
+
+    /// Find range in `TimeSeries`
+    ///
+    ///  * `range` - TODO: comment parm
+    ///  * return - Returns range in `TimeSeries` based on `range`
+    ///
+    fn in_range(&self, range: Range<Self::T>) -> Range<usize> {
+        // custom <fn time_searchable_time_series_t_in_range>
+        self.view().in_range(range)
+        // end <fn time_searchable_time_series_t_in_range>
+    }
+
+    /// Find range in `TimeSeries` of the times in `interval`
+    ///
+    ///  * `interval` - Interval whose bounds decide if its end times are included
+    ///  * return - Returns range in `TimeSeries` based on `interval`
+    ///
+    fn in_interval(&self, interval: &TimeInterval<Self::T>) -> Range<usize> {
+        // custom <fn time_searchable_time_series_t_in_interval>
+        self.view().in_interval(interval)
+        // end <fn time_searchable_time_series_t_in_interval>
+    }
+
+    // custom <impl TimeSearchable for TimeSeries<T>>
+    // end <impl TimeSearchable for TimeSeries<T>>
+}
+
+/// Provide search within a `TimeSeriesView`, giving positions in the view
+impl<'a, T, V> TimeSearchable for TimeSeriesView<'a, T, V>
+    where T: Time<T = T> + Ord,
+          V: Copy
+{
+    type T = T;
+    type TV = TimeValue<T, V>;
+
+    /// Finds range of `Self` that is `after` `time`
+    ///
+    ///  * `time` - Time being searched to idenify range `after`
+    ///  * return - Returns range to resulting items
+    ///
+    fn after(&self, time: Self::T) -> Range<usize> {
+        // custom <fn time_searchable_time_series_view_after>
 
         match self.binary_search_by(|tv| tv.time.cmp(&time)) {
             Ok(i) => (i + 1)..self.len(),
             Err(insert_index) => insert_index..self.len(),
         }
 
-        // end <fn time_searchable_time_series_t_after>
+        // end <fn time_searchable_time_series_view_after>
     }
 
     /// Finds range of `Self` that is `before` `time`
@@ -87,14 +176,14 @@ impl<T> TimeSearchable for TimeSeries<T>
     ///  * return - Returns range to resulting items
     ///
     fn before(&self, time: Self::T) -> Range<usize> {
-        // custom <fn time_searchable_time_series_t_before>
+        // custom <fn time_searchable_time_series_view_before>
 
         match self.binary_search_by(|tv| tv.time.cmp(&time)) {
             Ok(i) => 0..i,
             Err(insert_index) => 0..insert_index,
         }
 
-        // end <fn time_searchable_time_series_t_before>
+        // end <fn time_searchable_time_series_view_before>
     }
 
     /// Finds range of `Self` that is `on_or_after` `time`
@@ -103,14 +192,14 @@ impl<T> TimeSearchable for TimeSeries<T>
     ///  * return - Returns range to resulting items
     ///
     fn on_or_after(&self, time: Self::T) -> Range<usize> {
-        // custom <fn time_searchable_time_series_t_on_or_after>
+        // custom <fn time_searchable_time_series_view_on_or_after>
 
         match self.binary_search_by(|tv| tv.time.cmp(&time)) {
             Ok(i) => i..self.len(),
             Err(insert_index) => insert_index..self.len(),
         }
 
-        // end <fn time_searchable_time_series_t_on_or_after>
+        // end <fn time_searchable_time_series_view_on_or_after>
     }
 
     /// Finds range of `Self` that is `on_or_before` `time`
@@ -119,43 +208,82 @@ impl<T> TimeSearchable for TimeSeries<T>
     ///  * return - Returns range to resulting items
     ///
     fn on_or_before(&self, time: Self::T) -> Range<usize> {
-        // custom <fn time_searchable_time_series_t_on_or_before>
+        // custom <fn time_searchable_time_series_view_on_or_before>
 
         match self.binary_search_by(|tv| tv.time.cmp(&time)) {
             Ok(i) => 0..(i + 1),
             Err(insert_index) => 0..insert_index,
         }
 
-        // end <fn time_searchable_time_series_t_on_or_before>
+        // end <fn time_searchable_time_series_view_on_or_before>
     }
 
-    /// Find range in `TimeSeries`
+    /// Find range in `TimeSeriesView`
     ///
-    ///  * `range` - TODO: comment parm
-    ///  * return - Returns range in `TimeSeries` based on `range`
+    ///  * `range` - Times to find, including `range.start` but not `range.end`
+    ///  * return - Returns range in `TimeSeriesView` based on `range`
     ///
-    fn in_range<'a>(&'a self, range: Range<Self::T>) -> Range<usize> {
-        // custom <fn time_searchable_time_series_t_in_range>
+    fn in_range(&self, range: Range<Self::T>) -> Range<usize> {
+        // custom <fn time_searchable_time_series_view_in_range>
 
-        self.on_or_after(range.start).start..self.before(range.end).end
+        let start = self.on_or_after(range.start).start;
+        start..self.before(range.end).end.max(start)
 
-        // end <fn time_searchable_time_series_t_in_range>
+        // end <fn time_searchable_time_series_view_in_range>
     }
 
-    // custom <impl TimeSearchable for TimeSeries<T>>
-    // end <impl TimeSearchable for TimeSeries<T>>
+    /// Find range in `TimeSeriesView` of the times in `interval`
+    ///
+    ///  * `interval` - Interval whose bounds decide if its end times are included
+    ///  * return - Returns range in `TimeSeriesView` based on `interval`
+    ///
+    fn in_interval(&self, interval: &TimeInterval<Self::T>) -> Range<usize> {
+        // custom <fn time_searchable_time_series_view_in_interval>
+
+        search_interval(self, interval)
+
+        // end <fn time_searchable_time_series_view_in_interval>
+    }
+
+    // custom <impl TimeSearchable for TimeSeriesView<'a, T, V>>
+    // end <impl TimeSearchable for TimeSeriesView<'a, T, V>>
+}
+
+// --- module function definitions ---
+
+/// Finds the positions of the times in `interval` with the searches of `searchable`
+///
+///  * `searchable` - Rows ordered by time
+///  * `interval` - Interval whose bounds decide if its end times are included
+///  * return - Range of the positions in `searchable` of the times in `interval`
+///
+pub(crate) fn search_interval<S, T>(searchable: &S, interval: &TimeInterval<T>) -> Range<usize>
+    where S: TimeSearchable<T = T> + ?Sized,
+          T: Copy
+{
+    let start = match interval.start_bound {
+        IntervalBound::Open => searchable.after(interval.start).start,
+        IntervalBound::Closed => searchable.on_or_after(interval.start).start,
+    };
+    let end = match interval.end_bound {
+        IntervalBound::Open => searchable.before(interval.end).end,
+        IntervalBound::Closed => searchable.on_or_before(interval.end).end,
+    };
+    start..end.max(start)
 }
 
 /// Test module for time_searchable module
 #[cfg(test)]
 mod tests {
+    use Date;
+    use DateSeries;
     use Year;
     use super::*;
     mod time_searchable_time_series_t {
         use super::*;
 
         #[test]
-        fn after() -> () {
+        fn after() {
             // custom <test fn time_searchable_time_series_t_after>
 
             let v = v();
@@ -167,7 +295,7 @@ mod tests {
         }
 
         #[test]
-        fn before() -> () {
+        fn before() {
             // custom <test fn time_searchable_time_series_t_before>
 
 
@@ -180,7 +308,7 @@ mod tests {
         }
 
         #[test]
-        fn on_or_after() -> () {
+        fn on_or_after() {
             // custom <test fn time_searchable_time_series_t_on_or_after>
 
             let v = v();
@@ -192,7 +320,7 @@ mod tests {
         }
 
         #[test]
-        fn on_or_before() -> () {
+        fn on_or_before() {
             // custom <test fn time_searchable_time_series_t_on_or_before>
 
             let v = v();
@@ -204,7 +332,7 @@ mod tests {
         }
 
         #[test]
-        fn in_range() -> () {
+        fn in_range() {
             // custom <test fn time_searchable_time_series_t_in_range>
             let v = v();
             assert_eq!(v.in_range(0..0), 0..0);
@@ -216,30 +344,52 @@ mod tests {
             assert_eq!(v.in_range(2..4), 1..2);
             assert_eq!(v.in_range(2..6), 1..4);
 
+            let d = d();
+            assert_eq!(d.in_range(date(2017, 1, 1)..date(2017, 3, 1)), 0..2);
+            assert_eq!(d.in_range(date(2017, 1, 2)..date(2017, 3, 2)), 1..3);
+            assert_eq!(d.after(date(2017, 2, 1)), 2..3);
+
             // end <test fn time_searchable_time_series_t_in_range>
         }
 
         // custom <module ModuleCodeBlock.moduleBottom>
 
         fn v() -> TimeSeries<Year> {
-            TimeSeries {
-                data: vec![TimeValue {
-                               time: 1,
-                               value: 1.0,
-                           },
-                           TimeValue {
-                               time: 2,
-                               value: 2.0,
-                           },
-                           TimeValue {
-                               time: 4,
-                               value: 3.0,
-                           },
-                           TimeValue {
-                               time: 5,
-                               value: 4.0,
-                           }],
-            }
+            TimeSeries::try_from_vec(vec![TimeValue {
+                                              time: 1,
+                                              value: 1.0,
+                                          },
+                                          TimeValue {
+                                              time: 2,
+                                              value: 2.0,
+                                          },
+                                          TimeValue {
+                                              time: 4,
+                                              value: 3.0,
+                                          },
+                                          TimeValue {
+                                              time: 5,
+                                              value: 4.0,
+                                          }]).unwrap()
+        }
+
+        fn date(year: i32, month: u32, day: u32) -> Date {
+            Date::from_ymd_opt(year, month, day).unwrap()
+        }
+
+        fn d() -> DateSeries {
+            TimeSeries::try_from_vec(vec![TimeValue {
+                                              time: date(2017, 1, 1),
+                                              value: 1.0,
+                                          },
+                                          TimeValue {
+                                              time: date(2017, 2, 1),
+                                              value: 2.0,
+                                          },
+                                          TimeValue {
+                                              time: date(2017, 3, 1),
+                                              value: 3.0,
+                                          }]).unwrap()
         }
         // end <module ModuleCodeBlock.moduleBottom>
     }
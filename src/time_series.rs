@@ -3,51 +3,605 @@
 // --- module use statements ---
 
 use Add;
+use Date;
 use DateTime;
 use Deref;
-use NumOps;
+use FractionalYear;
+use Range;
 use Time;
+use TimeSearchable;
 use TimeValue;
 use Year;
+use YearMonth;
+use YearQuarter;
+use ZonedDateTime;
+use std::error::Error;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::DivAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+// --- module enum definitions ---
+
+/// How to combine values that share a time when building a `TimeSeries`
+///
+/// To sum the values at a time instead, build the series with `TimeSeries::new_summing`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DuplicatePolicy {
+    /// Reject the data
+    Error,
+    /// Keep the value that came first in the data
+    KeepFirst,
+    /// Keep the value that came last in the data
+    KeepLast,
+}
+
+/// Reasons data cannot form a `TimeSeries`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TimeSeriesError {
+    /// The time at `index` is earlier than the time before it
+    Unsorted {
+        /// Position of the out of order item
+        index: usize,
+    },
+    /// The time at `index` of the data given is the same as an earlier time
+    DuplicateTime {
+        /// Position of the repeated item
+        index: usize,
+    },
+}
 
 // --- module type aliases ---
 
+pub type DateSeries = TimeSeries<Date>;
 pub type DateTimeSeries = TimeSeries<DateTime>;
 pub type YearTimeSeries = TimeSeries<Year>;
+pub type FractionalYearTimeSeries = TimeSeries<FractionalYear>;
+pub type MonthTimeSeries = TimeSeries<YearMonth>;
+pub type QuarterTimeSeries = TimeSeries<YearQuarter>;
+pub type ZonedDateTimeSeries<Tz> = TimeSeries<ZonedDateTime<Tz>>;
 
 // custom <module ModuleCodeBlock.moduleBottom>
 
-#[derive(Debug, Default)]
-pub struct TimeSeries<T>
+/// Values ordered by strictly increasing time
+///
+/// The constructors establish the ordering that `TimeSearchable` relies on, and the data
+/// is private so the ordering holds for the life of the series.
+///
+/// Values are `f64` unless another `Copy` type is named. Searching, mutation and alignment
+/// work for any such type, while arithmetic is available where the value type supports it.
+#[derive(Debug, Clone)]
+pub struct TimeSeries<T, V = f64>
     where T: Time<T = T>
 {
-    pub data: Vec<TimeValue<T>>,
+    data: Vec<TimeValue<T, V>>,
 }
 
-impl<T> Deref for TimeSeries<T>
+impl<T, V> TimeSeries<T, V>
+    where T: Time<T = T>,
+          V: Copy
+{
+    /// Create a `TimeSeries` from data the caller knows is in strictly increasing time order
+    pub(crate) fn from_sorted(data: Vec<TimeValue<T, V>>) -> TimeSeries<T, V> {
+        TimeSeries { data }
+    }
+
+    /// Consumes the series, returning its data in time order
+    pub fn into_vec(self) -> Vec<TimeValue<T, V>> {
+        self.data
+    }
+
+    /// Applies `f` to each value, keeping the times
+    ///
+    ///  * `f` - Function of a value
+    ///  * return - Series of the results of `f`
+    ///
+    pub fn map_values<F>(&self, mut f: F) -> TimeSeries<T, V>
+        where F: FnMut(V) -> V
+    {
+        TimeSeries {
+            data: self.data
+                .iter()
+                .map(|tv| {
+                         TimeValue {
+                             time: tv.time,
+                             value: f(tv.value),
+                         }
+                     })
+                .collect(),
+        }
+    }
+
+    /// Replaces each value with the result of `f`
+    pub fn map_values_in_place<F>(&mut self, mut f: F)
+        where F: FnMut(V) -> V
+    {
+        for tv in &mut self.data {
+            tv.value = f(tv.value);
+        }
+    }
+}
+
+impl<T, V> TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Copy
+{
+    /// Create a `TimeSeries`, sorting `data` by time
+    ///
+    ///  * `data` - Values in any order
+    ///  * `duplicates` - How to treat values that share a time
+    ///  * return - The series, or an error giving the position in `data` of the first
+    ///    repeated time if `duplicates` rejects it
+    ///
+    pub fn new(data: Vec<TimeValue<T, V>>,
+               duplicates: DuplicatePolicy)
+               -> Result<TimeSeries<T, V>, TimeSeriesError> {
+        // custom <fn time_series_new>
+
+        TimeSeries::sort_merging(data, |last, value, index| {
+            match duplicates {
+                DuplicatePolicy::Error => return Err(TimeSeriesError::DuplicateTime { index }),
+                DuplicatePolicy::KeepFirst => (),
+                DuplicatePolicy::KeepLast => *last = value,
+            }
+            Ok(())
+        })
+
+        // end <fn time_series_new>
+    }
+
+    /// Create a `TimeSeries`, sorting `data` by time and combining values that share a time
+    ///
+    ///  * `data` - Values in any order
+    ///  * `merge` - Combines the value kept so far with the next value at the same time, such
+    ///    as `Add::add` to keep the sum
+    ///  * return - The series
+    ///
+    pub fn new_with<F>(data: Vec<TimeValue<T, V>>, mut merge: F) -> TimeSeries<T, V>
+        where F: FnMut(V, V) -> V
+    {
+        // custom <fn time_series_new_with>
+
+        TimeSeries::sort_merging(data, |last, value, _| {
+                *last = merge(*last, value);
+                Ok(())
+            })
+            .expect("merging duplicates cannot fail")
+
+        // end <fn time_series_new_with>
+    }
+
+    /// Create a `TimeSeries`, sorting `data` by time and summing values that share a time
+    ///
+    ///  * `data` - Values in any order
+    ///  * return - The series, with the sum of the values at each time
+    ///
+    pub fn new_summing(data: Vec<TimeValue<T, V>>) -> TimeSeries<T, V>
+        where V: Add<Output = V>
+    {
+        // custom <fn time_series_new_summing>
+        TimeSeries::new_with(data, Add::add)
+        // end <fn time_series_new_summing>
+    }
+
+    /// Create a `TimeSeries` from data that must already be in strictly increasing time order
+    ///
+    ///  * `data` - Values ordered by time
+    ///  * return - The series, or an error locating the first item out of order or repeated
+    ///
+    pub fn try_from_vec(data: Vec<TimeValue<T, V>>)
+                        -> Result<TimeSeries<T, V>, TimeSeriesError> {
+        // custom <fn time_series_try_from_vec>
+
+        for index in 1..data.len() {
+            if data[index].time < data[index - 1].time {
+                return Err(TimeSeriesError::Unsorted { index });
+            }
+            if data[index].time == data[index - 1].time {
+                return Err(TimeSeriesError::DuplicateTime { index });
+            }
+        }
+        Ok(TimeSeries { data })
+
+        // end <fn time_series_try_from_vec>
+    }
+
+    /// Adds `tv` at its place in time order
+    ///
+    /// Adding past the last time is an amortised O(1) push.
+    ///
+    ///  * `tv` - Value to add
+    ///  * return - An error, leaving the series unchanged, if its time is already present
+    ///
+    pub fn insert(&mut self, tv: TimeValue<T, V>) -> Result<(), TimeSeriesError> {
+        // custom <fn time_series_insert>
+
+        match self.position(&tv.time) {
+            Ok(index) => Err(TimeSeriesError::DuplicateTime { index }),
+            Err(index) => {
+                self.data.insert(index, tv);
+                Ok(())
+            }
+        }
+
+        // end <fn time_series_insert>
+    }
+
+    /// Sets the value at `time`, adding it if the time is not present
+    ///
+    ///  * `time` - Time of the value
+    ///  * `value` - Value to set
+    ///  * return - The value replaced, if any
+    ///
+    pub fn upsert_at(&mut self, time: T, value: V) -> Option<V> {
+        // custom <fn time_series_upsert_at>
+
+        match self.position(&time) {
+            Ok(index) => Some(::std::mem::replace(&mut self.data[index].value, value)),
+            Err(index) => {
+                self.data.insert(index, TimeValue { time, value });
+                None
+            }
+        }
+
+        // end <fn time_series_upsert_at>
+    }
+
+    /// Removes the value at `time`
+    ///
+    ///  * `time` - Time of the value to remove
+    ///  * return - The value removed, if the time was present
+    ///
+    pub fn remove_at(&mut self, time: T) -> Option<TimeValue<T, V>> {
+        self.position(&time).ok().map(|index| self.data.remove(index))
+    }
+
+    /// Keeps only the values with times in `range`
+    pub fn retain_range(&mut self, range: Range<T>) {
+        // custom <fn time_series_retain_range>
+        let keep = self.in_range(range);
+        // A range ending before it starts keeps nothing
+        self.data.truncate(keep.end.max(keep.start));
+        self.data.drain(..keep.start);
+        // end <fn time_series_retain_range>
+    }
+
+    /// Removes the values with times before `time`
+    pub fn truncate_before(&mut self, time: T) {
+        let end = self.before(time).end;
+        self.data.drain(..end);
+    }
+
+    /// Removes the values with times after `time`
+    pub fn truncate_after(&mut self, time: T) {
+        let start = self.after(time).start;
+        self.data.truncate(start);
+    }
+
+    /// Appends values that follow the series in strictly increasing time order
+    ///
+    ///  * `iter` - Values to append, each after the last time of the series
+    ///  * return - An error, leaving the series unchanged, locating the first value that is
+    ///    out of order or repeated
+    ///
+    pub fn extend_sorted<I>(&mut self, iter: I) -> Result<(), TimeSeriesError>
+        where I: IntoIterator<Item = TimeValue<T, V>>
+    {
+        // custom <fn time_series_extend_sorted>
+
+        let original_len = self.data.len();
+        for tv in iter {
+            if let Some(last) = self.data.last() {
+                let index = self.data.len();
+                let error = if tv.time < last.time {
+                    Some(TimeSeriesError::Unsorted { index })
+                } else if tv.time == last.time {
+                    Some(TimeSeriesError::DuplicateTime { index })
+                } else {
+                    None
+                };
+                if let Some(error) = error {
+                    self.data.truncate(original_len);
+                    return Err(error);
+                }
+            }
+            self.data.push(tv);
+        }
+        Ok(())
+
+        // end <fn time_series_extend_sorted>
+    }
+
+    // custom <impl TimeSeries>
+
+    /// Sorts `data` by time, handing each value that repeats a time to `on_duplicate` with
+    /// the value kept so far and its index in `data`
+    fn sort_merging<F>(data: Vec<TimeValue<T, V>>,
+                       mut on_duplicate: F)
+                       -> Result<TimeSeries<T, V>, TimeSeriesError>
+        where F: FnMut(&mut V, V, usize) -> Result<(), TimeSeriesError>
+    {
+        let mut data: Vec<(usize, TimeValue<T, V>)> = data.into_iter().enumerate().collect();
+        // A stable sort keeps repeated times in the order they were given
+        data.sort_by_key(|&(_, tv)| tv.time);
+        let mut result: Vec<TimeValue<T, V>> = Vec::with_capacity(data.len());
+        for (index, tv) in data {
+            match result.last_mut() {
+                Some(last) if last.time == tv.time => {
+                    on_duplicate(&mut last.value, tv.value, index)?
+                }
+                _ => result.push(tv),
+            }
+        }
+        Ok(TimeSeries { data: result })
+    }
+
+    /// Index of `time`, or the index it would be inserted at, checking the end first so
+    /// appends do not search
+    fn position(&self, time: &T) -> Result<usize, usize> {
+        match self.data.last() {
+            None => Err(0),
+            Some(last) if last.time < *time => Err(self.data.len()),
+            Some(_) => self.data.binary_search_by(|tv| tv.time.cmp(time)),
+        }
+    }
+
+    // end <impl TimeSeries>
+}
+
+/// An empty series
+impl<T, V> Default for TimeSeries<T, V>
+    where T: Time<T = T>
+{
+    fn default() -> TimeSeries<T, V> {
+        TimeSeries { data: Vec::new() }
+    }
+}
+
+/// Collect values in any order into a series, keeping the last value given for a repeated time
+impl<T, V> FromIterator<TimeValue<T, V>> for TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Copy
+{
+    fn from_iter<I>(iter: I) -> TimeSeries<T, V>
+        where I: IntoIterator<Item = TimeValue<T, V>>
+    {
+        TimeSeries::new(iter.into_iter().collect(), DuplicatePolicy::KeepLast)
+            .expect("keeping the last duplicate cannot fail")
+    }
+}
+
+impl<T, V> Deref for TimeSeries<T, V>
     where T: Time<T = T>
 {
-    type Target = Vec<TimeValue<T>>;
+    type Target = Vec<TimeValue<T, V>>;
 
     fn deref(&self) -> &Self::Target {
         &self.data
     }
 }
 
-impl<'a, T> Add<f64> for &'a TimeSeries<T>
-    where T: Time<T = T> + Add<f64, Output = T>
-{
-    type Output = TimeSeries<T>;
+impl fmt::Display for TimeSeriesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TimeSeriesError::Unsorted { index } => {
+                write!(f, "time at index {} is before the previous time", index)
+            }
+            TimeSeriesError::DuplicateTime { index } => {
+                write!(f, "time at index {} repeats an earlier time", index)
+            }
+        }
+    }
+}
 
-    fn add(self, rhs: f64) -> Self::Output {
-        let result = TimeSeries { data: Vec::with_capacity(self.len()) };
+impl Error for TimeSeriesError {}
 
-        for (i, &tv) in self.iter().enumerate() {
-            result[i] = tv + rhs;
+/// Implements a scalar operator on every value of owned and borrowed `TimeSeries`, with its
+/// assigning form, for values supporting the operator
+macro_rules! time_series_scalar_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<T, V> $op<V> for TimeSeries<T, V>
+            where T: Time<T = T>,
+                  V: $op<Output = V> + Copy
+        {
+            type Output = TimeSeries<T, V>;
+
+            fn $method(mut self, rhs: V) -> TimeSeries<T, V> {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl<'a, T, V> $op<V> for &'a TimeSeries<T, V>
+            where T: Time<T = T>,
+                  V: $op<Output = V> + Copy
+        {
+            type Output = TimeSeries<T, V>;
+
+            fn $method(self, rhs: V) -> TimeSeries<T, V> {
+                self.map_values(|value| value.$method(rhs))
+            }
+        }
+
+        impl<T, V> $op_assign<V> for TimeSeries<T, V>
+            where T: Time<T = T>,
+                  V: $op<Output = V> + Copy
+        {
+            fn $method_assign(&mut self, rhs: V) {
+                self.map_values_in_place(|value| value.$method(rhs));
+            }
         }
-        result
     }
 }
 
+time_series_scalar_op!(Add, add, AddAssign, add_assign);
+time_series_scalar_op!(Sub, sub, SubAssign, sub_assign);
+time_series_scalar_op!(Mul, mul, MulAssign, mul_assign);
+time_series_scalar_op!(Div, div, DivAssign, div_assign);
+
+impl<T, V> Neg for TimeSeries<T, V>
+    where T: Time<T = T>,
+          V: Neg<Output = V> + Copy
+{
+    type Output = TimeSeries<T, V>;
+
+    fn neg(mut self) -> TimeSeries<T, V> {
+        self.map_values_in_place(|value| -value);
+        self
+    }
+}
+
+impl<T, V> Neg for &TimeSeries<T, V>
+    where T: Time<T = T>,
+          V: Neg<Output = V> + Copy
+{
+    type Output = TimeSeries<T, V>;
+
+    fn neg(self) -> TimeSeries<T, V> {
+        self.map_values(|value| -value)
+    }
+}
 
 // end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for time_series module
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tv(time: Year, value: f64) -> TimeValue<Year> {
+        TimeValue { time, value }
+    }
+
+    fn unsorted() -> Vec<TimeValue<Year>> {
+        vec![tv(3, 1.0), tv(1, 2.0), tv(3, 3.0), tv(2, 4.0)]
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(TimeSeries::new(unsorted(), DuplicatePolicy::Error).unwrap_err(),
+                   TimeSeriesError::DuplicateTime { index: 2 });
+        assert_eq!(TimeSeries::new(unsorted(), DuplicatePolicy::KeepFirst).unwrap().into_vec(),
+                   vec![tv(1, 2.0), tv(2, 4.0), tv(3, 1.0)]);
+        assert_eq!(TimeSeries::new(unsorted(), DuplicatePolicy::KeepLast).unwrap().into_vec(),
+                   vec![tv(1, 2.0), tv(2, 4.0), tv(3, 3.0)]);
+        assert_eq!(TimeSeries::new_with(unsorted(), Add::add).into_vec(),
+                   vec![tv(1, 2.0), tv(2, 4.0), tv(3, 4.0)]);
+        assert_eq!(TimeSeries::new_summing(unsorted()).into_vec(),
+                   vec![tv(1, 2.0), tv(2, 4.0), tv(3, 4.0)]);
+        assert_eq!(TimeSeries::new_with(unsorted(), f64::max).into_vec(),
+                   vec![tv(1, 2.0), tv(2, 4.0), tv(3, 3.0)]);
+    }
+
+    #[test]
+    fn try_from_vec() {
+        assert_eq!(TimeSeries::try_from_vec(unsorted()).unwrap_err(),
+                   TimeSeriesError::Unsorted { index: 1 });
+        assert_eq!(TimeSeries::try_from_vec(vec![tv(1, 1.0), tv(1, 2.0)]).unwrap_err(),
+                   TimeSeriesError::DuplicateTime { index: 1 });
+        assert_eq!(TimeSeries::try_from_vec(vec![tv(1, 1.0), tv(2, 2.0)]).unwrap().len(), 2);
+        assert_eq!(TimeSeriesError::Unsorted { index: 1 }.to_string(),
+                   "time at index 1 is before the previous time");
+    }
+
+    #[test]
+    fn insert() {
+        let mut series = TimeSeries::try_from_vec(vec![tv(1, 1.0), tv(3, 3.0)]).unwrap();
+        assert_eq!(series.insert(tv(4, 4.0)), Ok(()));
+        assert_eq!(series.insert(tv(2, 2.0)), Ok(()));
+        assert_eq!(series.insert(tv(3, 9.0)),
+                   Err(TimeSeriesError::DuplicateTime { index: 2 }));
+        assert_eq!(*series, vec![tv(1, 1.0), tv(2, 2.0), tv(3, 3.0), tv(4, 4.0)]);
+
+        assert_eq!(series.upsert_at(3, 9.0), Some(3.0));
+        assert_eq!(series.upsert_at(0, 0.0), None);
+        assert_eq!(series.remove_at(2), Some(tv(2, 2.0)));
+        assert_eq!(series.remove_at(2), None);
+        assert_eq!(*series, vec![tv(0, 0.0), tv(1, 1.0), tv(3, 9.0), tv(4, 4.0)]);
+    }
+
+    #[test]
+    fn truncate() {
+        let series: TimeSeries<Year> = (1..8).map(|t| tv(t, t as f64)).collect();
+        let mut retained = series.clone();
+        retained.retain_range(3..6);
+        assert_eq!(retained.iter().map(|tv| tv.time).collect::<Vec<_>>(), vec![3, 4, 5]);
+
+        let mut truncated = series.clone();
+        truncated.truncate_before(3);
+        truncated.truncate_after(5);
+        assert_eq!(truncated.into_vec(), retained.into_vec());
+
+        let mut inverted = series.clone();
+        inverted.retain_range(Range { start: 6, end: 3 });
+        assert!(inverted.is_empty());
+    }
+
+    #[test]
+    fn extend_sorted() {
+        let mut series = TimeSeries::try_from_vec(vec![tv(1, 1.0)]).unwrap();
+        assert_eq!(series.extend_sorted(vec![tv(2, 2.0), tv(3, 3.0)]), Ok(()));
+        assert_eq!(series.extend_sorted(vec![tv(4, 4.0), tv(3, 3.0)]),
+                   Err(TimeSeriesError::Unsorted { index: 4 }));
+        assert_eq!(series.extend_sorted(vec![tv(3, 3.0)]),
+                   Err(TimeSeriesError::DuplicateTime { index: 3 }));
+        assert_eq!(series.len(), 3);
+    }
+
+    #[test]
+    fn scalar_ops() {
+        let series: TimeSeries<Year> = vec![tv(1, 2.0), tv(2, 4.0)].into_iter().collect();
+        assert_eq!(*(&series + 1.0), vec![tv(1, 3.0), tv(2, 5.0)]);
+        assert_eq!(*(&series - 1.0), vec![tv(1, 1.0), tv(2, 3.0)]);
+        assert_eq!(*(&series * 2.0), vec![tv(1, 4.0), tv(2, 8.0)]);
+        assert_eq!(*(&series / 2.0), vec![tv(1, 1.0), tv(2, 2.0)]);
+        assert_eq!(*-&series, vec![tv(1, -2.0), tv(2, -4.0)]);
+        assert_eq!(*(-(series.clone() * 3.0 + 1.0) / 7.0), vec![tv(1, -1.0), tv(2, -13.0 / 7.0)]);
+
+        let mut assigned = series.clone();
+        assigned += 1.0;
+        assigned *= 2.0;
+        assigned -= 2.0;
+        assigned /= 2.0;
+        assert_eq!(assigned.into_vec(), series.clone().into_vec());
+
+        assert_eq!(*series.map_values(f64::sqrt), vec![tv(1, 2.0_f64.sqrt()), tv(2, 2.0)]);
+        let mut squared = series.clone();
+        squared.map_values_in_place(|value| value * value);
+        assert_eq!(squared.into_vec(), vec![tv(1, 4.0), tv(2, 16.0)]);
+
+        let mut value = tv(1, 2.0);
+        value *= 3.0;
+        assert_eq!(-(&value - 1.0), tv(1, -5.0));
+        assert_eq!(value / 2.0, tv(1, 3.0));
+    }
+
+    #[test]
+    fn from_iter() {
+        let series: TimeSeries<Year> = unsorted().into_iter().collect();
+        assert_eq!(series.into_vec(), vec![tv(1, 2.0), tv(2, 4.0), tv(3, 3.0)]);
+        assert!(TimeSeries::<Year>::default().is_empty());
+    }
+
+    #[test]
+    fn other_values() {
+        let counts = TimeSeries::new_with(vec![TimeValue { time: 2, value: 1_i64 },
+                                               TimeValue { time: 1, value: 2 },
+                                               TimeValue { time: 2, value: 3 }],
+                                          Add::add);
+        assert_eq!(*(&counts * 10), vec![TimeValue { time: 1, value: 20 },
+                                         TimeValue { time: 2, value: 40 }]);
+
+        let mut single: TimeSeries<Year, f32> =
+            vec![TimeValue { time: 1, value: 1.5_f32 }].into_iter().collect();
+        single += 0.5;
+        assert_eq!(single.upsert_at(1, 4.0), Some(2.0_f32));
+        assert_eq!(single.on_or_after(0), 0..1);
+    }
+}
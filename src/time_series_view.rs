@@ -0,0 +1,224 @@
+//! Support for borrowed slices of a `TimeSeries`, so sub-periods are analysed without copying
+
+// --- module use statements ---
+
+use Deref;
+use Range;
+use Time;
+use TimeInterval;
+use TimeSearchable;
+use TimeSeries;
+use TimeValue;
+
+// --- module struct definitinos ---
+
+/// A borrowed run of consecutive values of a `TimeSeries`, in the same time order
+///
+/// Search results are positions within the view. The view dereferences to a slice of its
+/// values for iteration and indexing.
+#[derive(Debug)]
+pub struct TimeSeriesView<'a, T, V = f64>
+    where T: Time<T = T> + 'a,
+          V: 'a
+{
+    data: &'a [TimeValue<T, V>],
+}
+
+// --- module impl definitions ---
+
+impl<'a, T, V> TimeSeriesView<'a, T, V>
+    where T: Time<T = T>,
+          V: Copy
+{
+    /// Create a `TimeSeriesView` of data the caller knows is in strictly increasing time
+    /// order
+    pub(crate) fn from_sorted(data: &'a [TimeValue<T, V>]) -> TimeSeriesView<'a, T, V> {
+        TimeSeriesView { data }
+    }
+
+    /// The values at positions `range` of the view
+    pub fn slice(&self, range: Range<usize>) -> TimeSeriesView<'a, T, V> {
+        TimeSeriesView { data: &self.data[range] }
+    }
+
+    /// Copies the values of the view into a series of their own
+    pub fn to_series(&self) -> TimeSeries<T, V> {
+        TimeSeries::from_sorted(self.data.to_vec())
+    }
+}
+
+impl<'a, T, V> TimeSeriesView<'a, T, V>
+    where T: Time<T = T> + Ord,
+          V: Copy
+{
+    /// The values after `time`
+    pub fn view_after(&self, time: T) -> TimeSeriesView<'a, T, V> {
+        self.slice(self.after(time))
+    }
+
+    /// The values before `time`
+    pub fn view_before(&self, time: T) -> TimeSeriesView<'a, T, V> {
+        self.slice(self.before(time))
+    }
+
+    /// The values on or after `time`
+    pub fn view_on_or_after(&self, time: T) -> TimeSeriesView<'a, T, V> {
+        self.slice(self.on_or_after(time))
+    }
+
+    /// The values on or before `time`
+    pub fn view_on_or_before(&self, time: T) -> TimeSeriesView<'a, T, V> {
+        self.slice(self.on_or_before(time))
+    }
+
+    /// The values with times in `range`
+    pub fn view_in_range(&self, range: Range<T>) -> TimeSeriesView<'a, T, V> {
+        self.slice(self.in_range(range))
+    }
+
+    /// The values with times in `interval`
+    pub fn view_in_interval(&self, interval: &TimeInterval<T>) -> TimeSeriesView<'a, T, V> {
+        self.slice(self.in_interval(interval))
+    }
+
+    /// The latest value on or before `time`, the value as of `time`
+    pub fn latest_on_or_before(&self, time: T) -> Option<&'a TimeValue<T, V>> {
+        self.view_on_or_before(time).data.last()
+    }
+
+    /// The earliest value on or after `time`
+    pub fn earliest_on_or_after(&self, time: T) -> Option<&'a TimeValue<T, V>> {
+        self.view_on_or_after(time).data.first()
+    }
+}
+
+impl<T, V> TimeSeries<T, V>
+    where T: Time<T = T> + Ord,
+          V: Copy
+{
+    /// A view of every value of the series
+    pub fn view(&self) -> TimeSeriesView<'_, T, V> {
+        TimeSeriesView::from_sorted(self)
+    }
+
+    /// A view of the values after `time`
+    pub fn view_after(&self, time: T) -> TimeSeriesView<'_, T, V> {
+        self.view().view_after(time)
+    }
+
+    /// A view of the values before `time`
+    pub fn view_before(&self, time: T) -> TimeSeriesView<'_, T, V> {
+        self.view().view_before(time)
+    }
+
+    /// A view of the values on or after `time`
+    pub fn view_on_or_after(&self, time: T) -> TimeSeriesView<'_, T, V> {
+        self.view().view_on_or_after(time)
+    }
+
+    /// A view of the values on or before `time`
+    pub fn view_on_or_before(&self, time: T) -> TimeSeriesView<'_, T, V> {
+        self.view().view_on_or_before(time)
+    }
+
+    /// A view of the values with times in `range`
+    pub fn view_in_range(&self, range: Range<T>) -> TimeSeriesView<'_, T, V> {
+        self.view().view_in_range(range)
+    }
+
+    /// A view of the values with times in `interval`
+    pub fn view_in_interval(&self, interval: &TimeInterval<T>) -> TimeSeriesView<'_, T, V> {
+        self.view().view_in_interval(interval)
+    }
+
+    /// The latest value on or before `time`, the value as of `time`
+    pub fn latest_on_or_before(&self, time: T) -> Option<&TimeValue<T, V>> {
+        self.view().latest_on_or_before(time)
+    }
+
+    /// The earliest value on or after `time`
+    pub fn earliest_on_or_after(&self, time: T) -> Option<&TimeValue<T, V>> {
+        self.view().earliest_on_or_after(time)
+    }
+}
+
+impl<'a, T, V> Clone for TimeSeriesView<'a, T, V>
+    where T: Time<T = T>
+{
+    fn clone(&self) -> TimeSeriesView<'a, T, V> {
+        *self
+    }
+}
+
+impl<'a, T, V> Copy for TimeSeriesView<'a, T, V> where T: Time<T = T> {}
+
+impl<'a, T, V> Deref for TimeSeriesView<'a, T, V>
+    where T: Time<T = T>
+{
+    type Target = [TimeValue<T, V>];
+
+    fn deref(&self) -> &Self::Target {
+        self.data
+    }
+}
+
+impl<'a, T, V> IntoIterator for TimeSeriesView<'a, T, V>
+    where T: Time<T = T>
+{
+    type Item = &'a TimeValue<T, V>;
+    type IntoIter = ::std::slice::Iter<'a, TimeValue<T, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+/// Test module for time_series_view module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Interpolation;
+    use RateCurve;
+    use Year;
+
+    fn series() -> TimeSeries<Year> {
+        (1..8).map(|time| TimeValue { time, value: time as f64 / 100.0 }).collect()
+    }
+
+    fn times(view: TimeSeriesView<Year>) -> Vec<Year> {
+        view.into_iter().map(|tv| tv.time).collect()
+    }
+
+    #[test]
+    fn search() {
+        let series = series();
+        let middle = series.view_in_range(3..6);
+        assert_eq!(times(middle), vec![3, 4, 5]);
+        assert_eq!(times(series.view_after(5)), vec![6, 7]);
+        assert_eq!(times(series.view_on_or_before(2)), vec![1, 2]);
+        assert_eq!(times(series.view_in_interval(&TimeInterval::open_closed(5, 7))), vec![6, 7]);
+
+        assert_eq!(middle.on_or_after(4), 1..3);
+        assert_eq!(times(middle.view_before(5)), vec![3, 4]);
+        assert_eq!(times(middle.view_on_or_after(9)), vec![]);
+        assert_eq!(times(series.view_in_range(Range { start: 6, end: 3 })), vec![]);
+        assert_eq!(series.in_range(Range { start: 6, end: 3 }), 5..5);
+        assert_eq!(middle.to_series().into_vec(), middle.to_vec());
+        assert_eq!(middle[0].value, 0.03);
+
+        assert_eq!(middle.latest_on_or_before(9).map(|tv| tv.time), Some(5));
+        assert_eq!(middle.latest_on_or_before(2), None);
+        assert_eq!(series.earliest_on_or_after(4).map(|tv| tv.time), Some(4));
+        assert_eq!(series.earliest_on_or_after(8), None);
+    }
+
+    #[test]
+    fn read_only_api() {
+        let series = series();
+        let later = series.view_on_or_after(3);
+        assert_eq!(later.value_at(4, Interpolation::StepPrevious), Some(0.04));
+        assert_eq!(later.value_at(2, Interpolation::Linear), None);
+        assert_relative_eq!(later.discount_from_to(3, 5), series.discount_from_to(3, 5));
+        assert_relative_eq!(later.discount_from_to(3, 5), (0.03_f64 + 0.04).exp());
+    }
+}
@@ -0,0 +1,222 @@
+//! Support for stepping any time forward or back, so generic code can project through time
+
+// --- module use statements ---
+
+use Date;
+use Duration;
+use FractionalYear;
+use Tenor;
+use TenorShift;
+use Time;
+use Year;
+use YearMonth;
+use YearQuarter;
+use ZonedDateTime;
+use chrono::TimeZone;
+use std::convert::TryFrom;
+use zoned::from_local;
+
+// --- module trait definitions ---
+
+/// A time that can be moved by years or by a period
+pub trait TimeStep: Time<T = Self> + TenorShift {
+    /// Moves `self` by a number of years
+    ///
+    /// This is the inverse of `years_until`, to the resolution of the time type.
+    ///
+    ///  * `years` - Years to move, backwards if negative
+    ///  * return - `self` moved by `years`, rounded to the nearest representable time, or
+    ///    `None` if `years` is not finite or the time type cannot represent the result
+    ///
+    fn add_years(&self, years: f64) -> Option<Self>;
+
+    /// Moves `self` forward by `period`
+    ///
+    ///  * `period` - Period to move by
+    ///  * return - `self` moved by `period`, or `None` if the time type cannot represent it
+    ///
+    fn add_period(&self, period: &Tenor) -> Option<Self> {
+        self.add_tenor(period, false)
+    }
+
+    /// Moves `self` back by `period`
+    ///
+    ///  * `period` - Period to move by
+    ///  * return - `self` moved back by `period`, or `None` if the time type cannot represent it
+    ///    or `period` cannot be reversed
+    ///
+    fn sub_period(&self, period: &Tenor) -> Option<Self> {
+        self.add_tenor(&period.checked_neg()?, false)
+    }
+
+    // custom <trait_time_step>
+    // end <trait_time_step>
+}
+
+// --- module impl definitions ---
+
+/// Step a `Year` by whole years
+impl TimeStep for Year {
+    fn add_years(&self, years: f64) -> Option<Self> {
+        self.checked_add(Year::try_from(whole(years)?).ok()?)
+    }
+}
+
+/// Step a `YearMonth` by whole months
+impl TimeStep for YearMonth {
+    fn add_years(&self, years: f64) -> Option<Self> {
+        let months = i32::try_from(whole(years * 12.0)?).ok()?;
+        YearMonth::from_months(self.months().checked_add(months)?)
+    }
+}
+
+/// Step a `YearQuarter` by whole quarters
+impl TimeStep for YearQuarter {
+    fn add_years(&self, years: f64) -> Option<Self> {
+        let quarters = i32::try_from(whole(years * 4.0)?).ok()?;
+        YearQuarter::from_quarters(self.quarters().checked_add(quarters)?)
+    }
+}
+
+/// Step a `FractionalYear` exactly
+impl TimeStep for FractionalYear {
+    fn add_years(&self, years: f64) -> Option<Self> {
+        self.checked_add_years(years)
+    }
+}
+
+/// Step a `Date` by whole days, taking a year as 365 days like `years_until`
+impl TimeStep for Date {
+    fn add_years(&self, years: f64) -> Option<Self> {
+        self.checked_add_signed(Duration::try_days(whole(years * 365.0)?)?)
+    }
+}
+
+/// Step a `ZonedDateTime` by milliseconds of wall-clock time, taking a year as 365 days like
+/// `years_until`
+///
+/// Stepping the local time rather than the instant keeps `add_years` the inverse of
+/// `years_until` across daylight saving changes. A result in a skipped hour keeps the offset
+/// of `self`.
+impl<Tz> TimeStep for ZonedDateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Copy
+{
+    fn add_years(&self, years: f64) -> Option<Self> {
+        let step = Duration::try_milliseconds(whole(years * 365.0 * 86_400_000.0)?)?;
+        let local = self.naive_local().checked_add_signed(step)?;
+        Some(from_local(self, &local))
+    }
+}
+
+// --- module function definitions ---
+
+/// Rounds `value` to the nearest whole number
+///
+///  * `value` - Value to round
+///  * return - The rounded value, or `None` if `value` is not finite or is out of `i64` range
+///
+pub(crate) fn whole(value: f64) -> Option<i64> {
+    let rounded = value.round();
+    // `i64::MAX as f64` rounds up to 2^63, which is itself out of range
+    if rounded >= i64::MIN as f64 && rounded < i64::MAX as f64 {
+        Some(rounded as i64)
+    } else {
+        None
+    }
+}
+
+/// Test module for time_step module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TimeSeries;
+    use TimeValue;
+    use chrono::Utc;
+    use chrono_tz::America::New_York;
+
+    fn tenor(s: &str) -> Tenor {
+        s.parse().unwrap()
+    }
+
+    /// Grows `value` at `rate` per year for `steps` periods, written once for any `TimeStep`
+    fn project<T>(start: T, period: &Tenor, steps: usize, value: f64, rate: f64) -> TimeSeries<T>
+        where T: TimeStep + Ord
+    {
+        let mut data = vec![TimeValue { time: start, value }];
+        for _ in 0..steps {
+            let last = *data.last().unwrap();
+            let time = last.time.add_period(period).unwrap();
+            data.push(TimeValue {
+                          time,
+                          value: last.value * (1.0 + rate * last.time.years_until(&time)),
+                      });
+        }
+        TimeSeries::try_from_vec(data).unwrap()
+    }
+
+    #[test]
+    fn add_years() {
+        assert_eq!(2017.add_years(2.4), Some(2019));
+        assert_eq!(YearMonth::new(2017, 11).unwrap().add_years(0.25),
+                   YearMonth::new(2018, 2));
+        assert_eq!(YearQuarter::new(2017, 4).unwrap().add_years(-0.5),
+                   YearQuarter::new(2017, 2));
+        assert_eq!(FractionalYear::from(2031).add_years(0.5),
+                   Some(FractionalYear::from_years(2031.5)));
+
+        let date = Date::from_ymd_opt(2017, 1, 1).unwrap();
+        assert_relative_eq!(date.years_until(&date.add_years(1.5).unwrap()),
+                            1.5,
+                            epsilon = 1.0 / 365.0);
+        let noon = Utc.with_ymd_and_hms(2017, 1, 1, 12, 0, 0).unwrap();
+        assert_relative_eq!(noon.years_until(&noon.add_years(-0.75).unwrap()),
+                            -0.75,
+                            epsilon = 1e-9);
+    }
+
+    #[test]
+    fn add_years_out_of_range() {
+        assert_eq!(2017.add_years(f64::NAN), None);
+        assert_eq!(i32::MAX.add_years(1.0), None);
+        assert_eq!(2017.add_years(1e12), None);
+        assert_eq!(YearMonth::new(2017, 1).unwrap().add_years(f64::INFINITY), None);
+        assert_eq!(YearQuarter::new(2017, 1).unwrap().add_years(1e9), None);
+        assert_eq!(FractionalYear::from(2017).add_years(1e30), None);
+        assert_eq!(Date::MAX.add_years(1.0), None);
+        assert_eq!(Date::MIN.add_years(-1e300), None);
+        assert_eq!(Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap().add_years(1e7), None);
+    }
+
+    #[test]
+    fn add_years_across_daylight_saving() {
+        // New York moves its clocks forward on 2017-03-12
+        let before = New_York.with_ymd_and_hms(2017, 3, 11, 12, 0, 0).unwrap();
+        let after = before.add_years(1.0 / 365.0).unwrap();
+        assert_eq!(after, New_York.with_ymd_and_hms(2017, 3, 12, 12, 0, 0).unwrap());
+        assert_relative_eq!(before.years_until(&after), 1.0 / 365.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn periods() {
+        let date = Date::from_ymd_opt(2017, 3, 31).unwrap();
+        assert_eq!(date.add_period(&tenor("1M")), Date::from_ymd_opt(2017, 4, 30));
+        assert_eq!(date.sub_period(&tenor("1M")), Date::from_ymd_opt(2017, 2, 28));
+        assert_eq!(TimeStep::sub_period(&2017, &tenor("2Y")), Some(2015));
+        assert_eq!(TimeStep::sub_period(&2017, &tenor("1M")), None);
+        assert_eq!(date.sub_period(&tenor("-2147483648D")), None);
+    }
+
+    #[test]
+    fn generic_projection() {
+        let annual = project(2017, &tenor("1Y"), 3, 100.0, 0.1);
+        assert_relative_eq!(annual.last().unwrap().value, 133.1, epsilon = 1e-9);
+        assert_eq!(annual.last().unwrap().time, 2020);
+
+        let monthly = project(YearMonth::new(2017, 1).unwrap(), &tenor("1M"), 12, 100.0, 0.12);
+        assert_eq!(monthly.last().unwrap().time, YearMonth::new(2018, 1).unwrap());
+        assert_relative_eq!(monthly.last().unwrap().value,
+                            100.0 * 1.01_f64.powi(12),
+                            epsilon = 1e-9);
+    }
+}
@@ -2,24 +2,38 @@
 
 // --- module use statements ---
 
+use Act365Fixed;
 use Add;
-use DateTime;
+use Date;
+use DayCount;
 use Debug;
 use Into;
 use Year;
-use num::traits::NumOps;
+use ZonedDateTime;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::DivAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
 
 // --- module struct definitinos ---
 
 /// Associates a *time* with a *value* for time-series data
+///
+/// The value is an `f64` unless another type, such as `f32` or a decimal, is named.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
-pub struct TimeValue<T>
+pub struct TimeValue<T, V = f64>
     where T: Time<T = T>
 {
     /// Time associated with `self`
     pub time: T,
     /// Value associated with `self`
-    pub value: f64,
+    pub value: V,
 }
 
 // --- module trait definitions ---
@@ -33,14 +47,23 @@ pub trait Time: Debug + PartialEq + PartialOrd + Copy {
     ///
     ///  * return - The time associated with the data
     ///
-    fn time<'a>(&'a self) -> Self::T;
+    fn time(&self) -> Self::T;
 
     /// Years from self until `time`
     ///
     ///  * `time` - The `time` being compared, as in (`time` - self) in years
     ///  * return - Number of years from self until `time`
     ///
-    fn years_until<'a>(&'a self, time: &Self::T) -> f64;
+    fn years_until(&self, time: &Self::T) -> f64;
+
+    /// Years from self until `time` measured with `day_count`
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized;
 
     // custom <trait_time>
     // end <trait_time>
@@ -56,7 +79,7 @@ impl Time for Year {
     ///
     ///  * return - The time associated with the data
     ///
-    fn time<'a>(&'a self) -> Self::T {
+    fn time(&self) -> Self::T {
         // custom <fn time_year_time>
         *self
         // end <fn time_year_time>
@@ -67,7 +90,7 @@ impl Time for Year {
     ///  * `time` - The `time` being compared, as in (`time` - self) in years
     ///  * return - Number of years from self until `time`
     ///
-    fn years_until<'a>(&'a self, time: &Self::T) -> f64 {
+    fn years_until(&self, time: &Self::T) -> f64 {
         // custom <fn time_year_years_until>
 
         (time - *self).into()
@@ -75,45 +98,122 @@ impl Time for Year {
         // end <fn time_year_years_until>
     }
 
+    /// Years from self until `time` measured with `day_count`
+    ///
+    /// A `Year` is a whole number of years, so `day_count` has no effect
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, _day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_year_years_until_with>
+        self.years_until(time)
+        // end <fn time_year_years_until_with>
+    }
+
     // custom <impl Time for Year>
     // end <impl Time for Year>
 }
 
-/// `DateTime` is its own time component
-impl Time for DateTime {
+/// `ZonedDateTime` is its own time component, measured on the calendar of its zone
+impl<Tz> Time for ZonedDateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Copy
+{
     type T = Self;
 
     /// Acess the time field of data
     ///
     ///  * return - The time associated with the data
     ///
-    fn time<'a>(&'a self) -> Self::T {
+    fn time(&self) -> Self::T {
         // custom <fn time_date_time_time>
         *self
         // end <fn time_date_time_time>
     }
 
-    /// Years from self until `time`
+    /// Years from self until `time`, measured as *Actual/365 Fixed*
     ///
     ///  * `time` - The `time` being compared, as in (`time` - self) in years
     ///  * return - Number of years from self until `time`
     ///
-    fn years_until<'a>(&'a self, time: &Self::T) -> f64 {
+    fn years_until(&self, time: &Self::T) -> f64 {
         // custom <fn time_date_time_years_until>
+        self.years_until_with(time, &Act365Fixed)
+        // end <fn time_date_time_years_until>
+    }
 
-        let days: f64 = (self.signed_duration_since(*time).num_days() as u32).into();
-        return days / 364.22; // TODO: Make constant
+    /// Years from self until `time` measured with `day_count`
+    ///
+    /// Both times are measured by their wall-clock time in the zone of `self`
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_date_time_years_until_with>
+        day_count.year_fraction(&self.naive_local(),
+                                &time.with_timezone(&self.timezone()).naive_local())
+        // end <fn time_date_time_years_until_with>
+    }
 
-        // end <fn time_date_time_years_until>
+    // custom <impl Time for ZonedDateTime<Tz>>
+    // end <impl Time for ZonedDateTime<Tz>>
+}
+
+/// `Date` is its own time component
+impl Time for Date {
+    type T = Self;
+
+    /// Acess the time field of data
+    ///
+    ///  * return - The time associated with the data
+    ///
+    fn time(&self) -> Self::T {
+        // custom <fn time_date_time>
+        *self
+        // end <fn time_date_time>
     }
 
-    // custom <impl Time for DateTime>
-    // end <impl Time for DateTime>
+    /// Years from self until `time`, measured as *Actual/365 Fixed*
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until(&self, time: &Self::T) -> f64 {
+        // custom <fn time_date_years_until>
+        self.years_until_with(time, &Act365Fixed)
+        // end <fn time_date_years_until>
+    }
+
+    /// Years from self until `time` measured with `day_count`
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_date_years_until_with>
+        day_count.year_fraction(&self.and_time(NaiveTime::MIN), &time.and_time(NaiveTime::MIN))
+        // end <fn time_date_years_until_with>
+    }
+
+    // custom <impl Time for Date>
+    // end <impl Time for Date>
 }
 
-/// Provide access to the `time` component of `TimeValue<T>`
-impl<T> Time for TimeValue<T>
-    where T: Time<T = T> + Debug + PartialEq + Ord + Copy
+/// Provide access to the `time` component of `TimeValue<T, V>`
+impl<T, V> Time for TimeValue<T, V>
+    where T: Time<T = T> + Debug + PartialEq + Ord + Copy,
+          V: Debug + PartialOrd + Copy
 {
     type T = T;
 
@@ -121,7 +221,7 @@ impl<T> Time for TimeValue<T>
     ///
     ///  * return - The time associated with the data
     ///
-    fn time<'a>(&'a self) -> Self::T {
+    fn time(&self) -> Self::T {
         // custom <fn time_time_value_t_time>
         self.time
         // end <fn time_time_value_t_time>
@@ -132,25 +232,93 @@ impl<T> Time for TimeValue<T>
     ///  * `time` - The `time` being compared, as in (`time` - self) in years
     ///  * return - Number of years from self until `time`
     ///
-    fn years_until<'a>(&'a self, time: &Self::T) -> f64 {
+    fn years_until(&self, time: &Self::T) -> f64 {
         // custom <fn time_time_value_t_years_until>
         self.time().years_until(time)
         // end <fn time_time_value_t_years_until>
     }
 
-    // custom <impl Time for TimeValue<T>>
-    // end <impl Time for TimeValue<T>>
+    /// Years from self until `time` measured with `day_count`
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_time_value_t_years_until_with>
+        self.time().years_until_with(time, day_count)
+        // end <fn time_time_value_t_years_until_with>
+    }
+
+    // custom <impl Time for TimeValue<T, V>>
+    // end <impl Time for TimeValue<T, V>>
 }
 
 // custom <module ModuleCodeBlock.moduleBottom>
 
-impl<'a, T> Add<f64> for &'a TimeValue<T>
-    where T: Time<T = T>
+/// Implements a scalar operator on the value of owned and borrowed `TimeValue`, with its
+/// assigning form, for values supporting the operator
+macro_rules! time_value_scalar_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<T, V> $op<V> for TimeValue<T, V>
+            where T: Time<T = T>,
+                  V: $op<Output = V>
+        {
+            type Output = TimeValue<T, V>;
+
+            fn $method(self, rhs: V) -> TimeValue<T, V> {
+                TimeValue { time: self.time, value: self.value.$method(rhs) }
+            }
+        }
+
+        impl<'a, T, V> $op<V> for &'a TimeValue<T, V>
+            where T: Time<T = T>,
+                  V: $op<Output = V> + Copy
+        {
+            type Output = TimeValue<T, V>;
+
+            fn $method(self, rhs: V) -> TimeValue<T, V> {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl<T, V> $op_assign<V> for TimeValue<T, V>
+            where T: Time<T = T>,
+                  V: $op_assign
+        {
+            fn $method_assign(&mut self, rhs: V) {
+                self.value.$method_assign(rhs);
+            }
+        }
+    }
+}
+
+time_value_scalar_op!(Add, add, AddAssign, add_assign);
+time_value_scalar_op!(Sub, sub, SubAssign, sub_assign);
+time_value_scalar_op!(Mul, mul, MulAssign, mul_assign);
+time_value_scalar_op!(Div, div, DivAssign, div_assign);
+
+impl<T, V> Neg for TimeValue<T, V>
+    where T: Time<T = T>,
+          V: Neg<Output = V>
+{
+    type Output = TimeValue<T, V>;
+
+    fn neg(self) -> TimeValue<T, V> {
+        TimeValue { time: self.time, value: -self.value }
+    }
+}
+
+impl<T, V> Neg for &TimeValue<T, V>
+    where T: Time<T = T>,
+          V: Neg<Output = V> + Copy
 {
-    type Output = TimeValue<T>;
+    type Output = TimeValue<T, V>;
 
-    fn add(self, rhs: f64) -> Self::Output {
-        TimeValue { time: self.time, value: self.value + rhs }
+    fn neg(self) -> TimeValue<T, V> {
+        -*self
     }
 }
 
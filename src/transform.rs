@@ -0,0 +1,281 @@
+//! Support for cumulative and period-over-period transforms, such as prices to returns or
+//! balances to flows
+
+// --- module use statements ---
+
+use DuplicatePolicy;
+use Tenor;
+use Time;
+use TimeSearchable;
+use TimeSeries;
+use TimeStep;
+use TimeValue;
+
+// --- module enum definitions ---
+
+/// How a time is treated when its result needs an observation outside the data, such as the
+/// first time of a difference
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Edge {
+    /// Leave the time out of the result
+    Drop,
+    /// Keep the time with a `NaN` value
+    Nan,
+    /// Keep the time with the given value
+    Fill(f64),
+}
+
+// --- module impl definitions ---
+
+impl<T> TimeSeries<T>
+    where T: Time<T = T> + Ord
+{
+    /// Running total of the values
+    pub fn cumsum(&self) -> TimeSeries<T> {
+        let mut total = 0.0;
+        self.map_values(|value| {
+                            total += value;
+                            total
+                        })
+    }
+
+    /// Running product of the values
+    pub fn cumprod(&self) -> TimeSeries<T> {
+        let mut product = 1.0;
+        self.map_values(|value| {
+                            product *= value;
+                            product
+                        })
+    }
+
+    /// Change in value from the previous observation, turning balances into flows
+    ///
+    ///  * `first` - Treatment of the first time, which has no previous observation
+    ///  * return - Series of differences at the original times
+    ///
+    pub fn diff(&self, first: Edge) -> TimeSeries<T> {
+        self.period_over_period(first, |previous, value| value - previous)
+    }
+
+    /// Fractional change in value from the previous observation
+    ///
+    ///  * `first` - Treatment of the first time, which has no previous observation
+    ///  * return - Series of changes at the original times
+    ///
+    pub fn pct_change(&self, first: Edge) -> TimeSeries<T> {
+        self.period_over_period(first, |previous, value| value / previous - 1.0)
+    }
+
+    /// Natural logarithm of the ratio of each value to the previous one
+    ///
+    ///  * `first` - Treatment of the first time, which has no previous observation
+    ///  * return - Series of log returns at the original times
+    ///
+    pub fn log_returns(&self, first: Edge) -> TimeSeries<T> {
+        self.period_over_period(first, |previous, value| (value / previous).ln())
+    }
+
+    // custom <impl TimeSeries>
+
+    /// Applies `f` to each value and the one before it
+    fn period_over_period<F>(&self, first: Edge, f: F) -> TimeSeries<T>
+        where F: Fn(f64, f64) -> f64
+    {
+        let mut data = Vec::with_capacity(self.len());
+        if let Some(tv) = self.first() {
+            push_edge(&mut data, tv.time, first);
+        }
+        for pair in self.windows(2) {
+            data.push(TimeValue {
+                          time: pair[1].time,
+                          value: f(pair[0].value, pair[1].value),
+                      });
+        }
+        TimeSeries::from_sorted(data)
+    }
+
+    // end <impl TimeSeries>
+}
+
+impl<T> TimeSeries<T>
+    where T: TimeStep + Ord
+{
+    /// Moves every time by `period`, keeping the values
+    ///
+    /// Times that land on the same time, such as the ends of two months moved into a shorter
+    /// month, keep the later value.
+    ///
+    ///  * `period` - Period to move the times by
+    ///  * return - The moved series, or `None` if a time cannot be moved
+    ///
+    pub fn shift(&self, period: &Tenor) -> Option<TimeSeries<T>> {
+        // custom <fn time_series_shift>
+        let data = self.iter()
+            .map(|tv| tv.time.add_period(period).map(|time| TimeValue { time, value: tv.value }))
+            .collect::<Option<Vec<_>>>()?;
+        TimeSeries::new(data, DuplicatePolicy::KeepLast).ok()
+        // end <fn time_series_shift>
+    }
+
+    /// Value of the series `period` before each time, such as last year's value
+    ///
+    ///  * `period` - How far back to look
+    ///  * `edge` - Treatment of times with no observation on or before the earlier time
+    ///  * return - Series at the original times holding the latest value on or before each
+    ///    time moved back by `period`, or `None` if a time cannot be moved
+    ///
+    pub fn lag(&self, period: &Tenor, edge: Edge) -> Option<TimeSeries<T>> {
+        // custom <fn time_series_lag>
+        self.look(edge, |time| time.sub_period(period))
+        // end <fn time_series_lag>
+    }
+
+    /// Value of the series `period` after each time, such as next year's value
+    ///
+    ///  * `period` - How far forward to look
+    ///  * `edge` - Treatment of times looking past the last observation
+    ///  * return - Series at the original times holding the latest value on or before each
+    ///    time moved forward by `period`, or `None` if a time cannot be moved
+    ///
+    pub fn lead(&self, period: &Tenor, edge: Edge) -> Option<TimeSeries<T>> {
+        // custom <fn time_series_lead>
+        self.look(edge, |time| time.add_period(period))
+        // end <fn time_series_lead>
+    }
+
+    // custom <impl TimeSeries>
+
+    /// Series at the original times of the value as of the time given by `target`, or `None`
+    /// if `target` fails for a time
+    fn look<F>(&self, edge: Edge, target: F) -> Option<TimeSeries<T>>
+        where F: Fn(&T) -> Option<T>
+    {
+        let mut data = Vec::with_capacity(self.len());
+        let last = self.last().map(|tv| tv.time);
+        for tv in self.iter() {
+            let time = target(&tv.time)?;
+            let found = Some(time)
+                .filter(|time| Some(*time) <= last)
+                .and_then(|time| self.on_or_before(time).end.checked_sub(1));
+            match found {
+                Some(i) => {
+                    data.push(TimeValue {
+                                  time: tv.time,
+                                  value: self[i].value,
+                              })
+                }
+                None => push_edge(&mut data, tv.time, edge),
+            }
+        }
+        Some(TimeSeries::from_sorted(data))
+    }
+
+    // end <impl TimeSeries>
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// Adds `time` to `data` as `edge` directs
+fn push_edge<T>(data: &mut Vec<TimeValue<T>>, time: T, edge: Edge)
+    where T: Time<T = T>
+{
+    match edge {
+        Edge::Drop => (),
+        Edge::Nan => data.push(TimeValue { time, value: f64::NAN }),
+        Edge::Fill(value) => data.push(TimeValue { time, value }),
+    }
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for transform module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Date;
+    use Year;
+    use YearMonth;
+
+    fn series(points: &[(Year, f64)]) -> TimeSeries<Year> {
+        points.iter().map(|&(time, value)| TimeValue { time, value }).collect()
+    }
+
+    fn points<T: Time<T = T>>(series: &TimeSeries<T>) -> Vec<(T, f64)> {
+        series.iter().map(|tv| (tv.time, tv.value)).collect()
+    }
+
+    fn prices() -> TimeSeries<Year> {
+        series(&[(2017, 100.0), (2018, 110.0), (2019, 99.0)])
+    }
+
+    fn tenor(s: &str) -> Tenor {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn cumulative() {
+        assert_eq!(points(&prices().diff(Edge::Fill(0.0)).cumsum()),
+                   vec![(2017, 0.0), (2018, 10.0), (2019, -1.0)]);
+        assert_eq!(points(&series(&[(1, 2.0), (2, 3.0), (3, 0.5)]).cumprod()),
+                   vec![(1, 2.0), (2, 6.0), (3, 3.0)]);
+    }
+
+    #[test]
+    fn period_over_period() {
+        let prices = prices();
+        assert_eq!(points(&prices.diff(Edge::Drop)), vec![(2018, 10.0), (2019, -11.0)]);
+        let changes = prices.pct_change(Edge::Nan);
+        assert!(changes[0].value.is_nan());
+        assert_relative_eq!(changes[1].value, 0.1);
+        assert_relative_eq!(changes[2].value, -0.1);
+        let returns = prices.log_returns(Edge::Fill(0.0));
+        assert_eq!(returns[0].value, 0.0);
+        assert_relative_eq!(returns[1].value + returns[2].value, (0.99_f64).ln());
+        assert!(TimeSeries::<Year>::default().diff(Edge::Nan).is_empty());
+    }
+
+    #[test]
+    fn shift() {
+        assert_eq!(points(&prices().shift(&tenor("1Y")).unwrap()),
+                   vec![(2018, 100.0), (2019, 110.0), (2020, 99.0)]);
+        assert!(prices().shift(&tenor("1M")).is_none());
+
+        let date = |m, d| Date::from_ymd_opt(2017, m, d).unwrap();
+        let month_ends: TimeSeries<Date> = vec![TimeValue {
+                                                    time: date(1, 30),
+                                                    value: 1.0,
+                                                },
+                                                TimeValue {
+                                                    time: date(1, 31),
+                                                    value: 2.0,
+                                                }]
+            .into_iter()
+            .collect();
+        assert_eq!(points(&month_ends.shift(&tenor("1M")).unwrap()), vec![(date(2, 28), 2.0)]);
+    }
+
+    #[test]
+    fn lag_and_lead() {
+        let month = |m| YearMonth::new(2017, m).unwrap();
+        let sales: TimeSeries<YearMonth> =
+            (1..7).map(|m| TimeValue { time: month(m), value: m as f64 }).collect();
+        let quarter = tenor("3M");
+        assert_eq!(points(&sales.lag(&quarter, Edge::Drop).unwrap()),
+                   vec![(month(4), 1.0), (month(5), 2.0), (month(6), 3.0)]);
+        assert_eq!(points(&sales.lead(&quarter, Edge::Fill(0.0)).unwrap()),
+                   vec![(month(1), 4.0),
+                        (month(2), 5.0),
+                        (month(3), 6.0),
+                        (month(4), 0.0),
+                        (month(5), 0.0),
+                        (month(6), 0.0)]);
+
+        let irregular = series(&[(2010, 1.0), (2013, 2.0), (2014, 3.0)]);
+        assert_eq!(points(&irregular.lag(&tenor("2Y"), Edge::Drop).unwrap()),
+                   vec![(2013, 1.0), (2014, 1.0)]);
+        assert!(irregular.lag(&tenor("6M"), Edge::Drop).is_none());
+        assert!(sales.lead(&tenor("1W"), Edge::Nan).is_none());
+    }
+}
@@ -0,0 +1,211 @@
+//! Support for monthly time data
+
+// --- module use statements ---
+
+use Date;
+use DayCount;
+use Time;
+use chrono::Datelike;
+use std::fmt;
+
+// --- module struct definitinos ---
+
+/// A calendar month of a year, for monthly time-series data
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct YearMonth {
+    /// Calendar year
+    year: i32,
+    /// Month of the year, 1 through 12
+    month: u32,
+}
+
+// --- module impl definitions ---
+
+/// `YearMonth` is its own time component
+impl Time for YearMonth {
+    type T = Self;
+
+    /// Acess the time field of data
+    ///
+    ///  * return - The time associated with the data
+    ///
+    fn time(&self) -> Self::T {
+        // custom <fn time_year_month_time>
+        *self
+        // end <fn time_year_month_time>
+    }
+
+    /// Years from self until `time`, as the exact number of months over 12
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until(&self, time: &Self::T) -> f64 {
+        // custom <fn time_year_month_years_until>
+        f64::from(time.months() - self.months()) / 12.0
+        // end <fn time_year_month_years_until>
+    }
+
+    /// Years from self until `time` measured with `day_count`
+    ///
+    /// A `YearMonth` is a whole number of months, so `day_count` has no effect
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, _day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_year_month_years_until_with>
+        self.years_until(time)
+        // end <fn time_year_month_years_until_with>
+    }
+}
+
+impl YearMonth {
+    /// Create a `YearMonth`
+    ///
+    ///  * `year` - Calendar year
+    ///  * `month` - Month of the year, 1 through 12
+    ///  * return - The `YearMonth`, or `None` if `month` is out of range or `year` is outside
+    ///    the years a `Date` can represent
+    ///
+    pub fn new(year: i32, month: u32) -> Option<YearMonth> {
+        // custom <fn year_month_new>
+        if (1..=12).contains(&month) && (Date::MIN.year()..=Date::MAX.year()).contains(&year) {
+            Some(YearMonth { year, month })
+        } else {
+            None
+        }
+        // end <fn year_month_new>
+    }
+
+    /// The `YearMonth` containing `date`
+    ///
+    ///  * `date` - Date to find the month of
+    ///  * return - The month containing `date`
+    ///
+    pub fn from_date(date: &Date) -> YearMonth {
+        // custom <fn year_month_from_date>
+        YearMonth {
+            year: date.year(),
+            month: date.month(),
+        }
+        // end <fn year_month_from_date>
+    }
+
+    /// Calendar year of the month
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Month of the year, 1 through 12
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// First day of the month
+    ///
+    ///  * return - The date of the first day of the month
+    ///
+    pub fn first_day(&self) -> Date {
+        // custom <fn year_month_first_day>
+        Date::from_ymd_opt(self.year, self.month, 1).expect("year checked on creation")
+        // end <fn year_month_first_day>
+    }
+
+    /// Last day of the month
+    ///
+    ///  * return - The date of the last day of the month
+    ///
+    pub fn last_day(&self) -> Date {
+        // custom <fn year_month_last_day>
+        let last_day = if self.month == 12 {
+            Date::from_ymd_opt(self.year, 12, 31)
+        } else {
+            Date::from_ymd_opt(self.year, self.month + 1, 1).and_then(|date| date.pred_opt())
+        };
+        last_day.expect("year checked on creation")
+        // end <fn year_month_last_day>
+    }
+
+    // custom <impl YearMonth>
+
+    /// Count of months since the start of year 0
+    pub(crate) fn months(&self) -> i32 {
+        self.year * 12 + self.month as i32 - 1
+    }
+
+
+    /// The `YearMonth` `months` months after the start of year 0, or `None` if its year is
+    /// outside the years a `Date` can represent
+    pub(crate) fn from_months(months: i32) -> Option<YearMonth> {
+        YearMonth::new(months.div_euclid(12), months.rem_euclid(12) as u32 + 1)
+    }
+
+    // end <impl YearMonth>
+}
+
+impl fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Test module for year_month module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TimeSearchable;
+    use TimeSeries;
+    use TimeValue;
+
+    #[test]
+    fn new() {
+        assert_eq!(YearMonth::new(2017, 0), None);
+        assert_eq!(YearMonth::new(2017, 13), None);
+        assert_eq!(YearMonth::new(Date::MAX.year() + 1, 1), None);
+        assert_eq!(YearMonth::new(Date::MAX.year(), 12).map(|ym| ym.last_day()),
+                   Some(Date::MAX));
+        assert_eq!(YearMonth::new(Date::MIN.year(), 1).map(|ym| ym.first_day()),
+                   Some(Date::MIN));
+        assert_eq!(YearMonth::new(2017, 12).map(|ym| ym.to_string()),
+                   Some("2017-12".to_string()));
+    }
+
+    #[test]
+    fn years_until() {
+        let oct = YearMonth::new(2017, 10).unwrap();
+        let jan = YearMonth::new(2018, 1).unwrap();
+        assert_relative_eq!(oct.years_until(&jan), 0.25);
+        assert_relative_eq!(jan.years_until(&oct), -0.25);
+    }
+
+    #[test]
+    fn from_date() {
+        let date = Date::from_ymd_opt(2017, 10, 17).unwrap();
+        let month = YearMonth::from_date(&date);
+        assert_eq!((month.year(), month.month()), (2017, 10));
+        assert_eq!(month.first_day(), Date::from_ymd_opt(2017, 10, 1).unwrap());
+        assert_eq!(month.last_day(), Date::from_ymd_opt(2017, 10, 31).unwrap());
+        assert_eq!(YearMonth::new(2016, 2).unwrap().last_day(),
+                   Date::from_ymd_opt(2016, 2, 29).unwrap());
+        assert_eq!(YearMonth::new(2017, 12).unwrap().last_day(),
+                   Date::from_ymd_opt(2017, 12, 31).unwrap());
+    }
+
+    #[test]
+    fn searchable() {
+        let month = |m| YearMonth::new(2017, m).unwrap();
+        let series = TimeSeries::try_from_vec((1..13)
+                                            .map(|m| {
+                                                     TimeValue {
+                                                         time: month(m),
+                                                         value: m as f64,
+                                                     }
+                                                 })
+                                            .collect()).unwrap();
+        assert_eq!(series.in_range(month(4)..month(7)), 3..6);
+    }
+}
@@ -0,0 +1,206 @@
+//! Support for quarterly time data
+
+// --- module use statements ---
+
+use Date;
+use DayCount;
+use Time;
+use YearMonth;
+use std::fmt;
+
+// --- module struct definitinos ---
+
+/// A calendar quarter of a year, for quarterly time-series data
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct YearQuarter {
+    /// Calendar year
+    year: i32,
+    /// Quarter of the year, 1 through 4
+    quarter: u32,
+}
+
+// --- module impl definitions ---
+
+/// `YearQuarter` is its own time component
+impl Time for YearQuarter {
+    type T = Self;
+
+    /// Acess the time field of data
+    ///
+    ///  * return - The time associated with the data
+    ///
+    fn time(&self) -> Self::T {
+        // custom <fn time_year_quarter_time>
+        *self
+        // end <fn time_year_quarter_time>
+    }
+
+    /// Years from self until `time`, as the exact number of quarters over 4
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until(&self, time: &Self::T) -> f64 {
+        // custom <fn time_year_quarter_years_until>
+        f64::from(time.quarters() - self.quarters()) / 4.0
+        // end <fn time_year_quarter_years_until>
+    }
+
+    /// Years from self until `time` measured with `day_count`
+    ///
+    /// A `YearQuarter` is a whole number of quarters, so `day_count` has no effect
+    ///
+    ///  * `time` - The `time` being compared, as in (`time` - self) in years
+    ///  * `day_count` - Convention used to measure the years
+    ///  * return - Number of years from self until `time`
+    ///
+    fn years_until_with<DC>(&self, time: &Self::T, _day_count: &DC) -> f64
+        where DC: DayCount + ?Sized
+    {
+        // custom <fn time_year_quarter_years_until_with>
+        self.years_until(time)
+        // end <fn time_year_quarter_years_until_with>
+    }
+}
+
+impl YearQuarter {
+    /// Create a `YearQuarter`
+    ///
+    ///  * `year` - Calendar year
+    ///  * `quarter` - Quarter of the year, 1 through 4
+    ///  * return - The `YearQuarter`, or `None` if `quarter` is out of range or `year` is
+    ///    outside the years a `Date` can represent
+    ///
+    pub fn new(year: i32, quarter: u32) -> Option<YearQuarter> {
+        // custom <fn year_quarter_new>
+        if (1..=4).contains(&quarter) && YearMonth::new(year, 1).is_some() {
+            Some(YearQuarter { year, quarter })
+        } else {
+            None
+        }
+        // end <fn year_quarter_new>
+    }
+
+    /// The `YearQuarter` containing `date`
+    ///
+    ///  * `date` - Date to find the quarter of
+    ///  * return - The quarter containing `date`
+    ///
+    pub fn from_date(date: &Date) -> YearQuarter {
+        // custom <fn year_quarter_from_date>
+        YearQuarter::from(YearMonth::from_date(date))
+        // end <fn year_quarter_from_date>
+    }
+
+    /// Calendar year of the quarter
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Quarter of the year, 1 through 4
+    pub fn quarter(&self) -> u32 {
+        self.quarter
+    }
+
+    /// First month of the quarter
+    ///
+    ///  * return - The month the quarter starts in
+    ///
+    pub fn first_month(&self) -> YearMonth {
+        // custom <fn year_quarter_first_month>
+        YearMonth::new(self.year, self.quarter * 3 - 2).expect("quarter is 1 through 4")
+        // end <fn year_quarter_first_month>
+    }
+
+    /// First day of the quarter
+    ///
+    ///  * return - The date of the first day of the quarter
+    ///
+    pub fn first_day(&self) -> Date {
+        // custom <fn year_quarter_first_day>
+        self.first_month().first_day()
+        // end <fn year_quarter_first_day>
+    }
+
+    // custom <impl YearQuarter>
+
+    /// Count of quarters since the start of year 0
+    pub(crate) fn quarters(&self) -> i32 {
+        self.year * 4 + self.quarter as i32 - 1
+    }
+
+
+    /// The `YearQuarter` `quarters` quarters after the start of year 0, or `None` if its year
+    /// is outside the years a `Date` can represent
+    pub(crate) fn from_quarters(quarters: i32) -> Option<YearQuarter> {
+        YearQuarter::new(quarters.div_euclid(4), quarters.rem_euclid(4) as u32 + 1)
+    }
+
+    // end <impl YearQuarter>
+}
+
+/// The quarter containing the month
+impl From<YearMonth> for YearQuarter {
+    fn from(month: YearMonth) -> YearQuarter {
+        YearQuarter {
+            year: month.year(),
+            quarter: (month.month() - 1) / 3 + 1,
+        }
+    }
+}
+
+impl fmt::Display for YearQuarter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-Q{}", self.year, self.quarter)
+    }
+}
+
+/// Test module for year_quarter module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TimeSearchable;
+    use TimeSeries;
+    use TimeValue;
+
+    #[test]
+    fn new() {
+        assert_eq!(YearQuarter::new(2017, 0), None);
+        assert_eq!(YearQuarter::new(2017, 5), None);
+        assert_eq!(YearQuarter::new(i32::MAX, 1), None);
+        assert_eq!(YearQuarter::new(2017, 4).map(|yq| yq.to_string()),
+                   Some("2017-Q4".to_string()));
+    }
+
+    #[test]
+    fn years_until() {
+        let q4 = YearQuarter::new(2017, 4).unwrap();
+        let q2 = YearQuarter::new(2018, 2).unwrap();
+        assert_relative_eq!(q4.years_until(&q2), 0.5);
+        assert_relative_eq!(q2.years_until(&q4), -0.5);
+    }
+
+    #[test]
+    fn from_date() {
+        let quarter = YearQuarter::from_date(&Date::from_ymd_opt(2017, 8, 17).unwrap());
+        assert_eq!((quarter.year(), quarter.quarter()), (2017, 3));
+        assert_eq!(quarter.first_month(), YearMonth::new(2017, 7).unwrap());
+        assert_eq!(quarter.first_day(), Date::from_ymd_opt(2017, 7, 1).unwrap());
+        assert_eq!(YearQuarter::from(YearMonth::new(2017, 12).unwrap()),
+                   YearQuarter::new(2017, 4).unwrap());
+    }
+
+    #[test]
+    fn searchable() {
+        let quarter = |q| YearQuarter::new(2017, q).unwrap();
+        let series = TimeSeries::try_from_vec((1..5)
+                                            .map(|q| {
+                                                     TimeValue {
+                                                         time: quarter(q),
+                                                         value: q as f64,
+                                                     }
+                                                 })
+                                            .collect()).unwrap();
+        assert_eq!(series.on_or_after(quarter(3)), 2..4);
+    }
+}
@@ -0,0 +1,162 @@
+//! Support for time-series data whose times carry a time zone
+
+// --- module use statements ---
+
+use Date;
+use Range;
+use TimeSeries;
+use TimeValue;
+use ZonedDateTime;
+use chrono::LocalResult;
+use chrono::NaiveDateTime;
+use chrono::Offset;
+use chrono::TimeZone;
+
+// --- module impl definitions ---
+
+impl<Tz> TimeSeries<ZonedDateTime<Tz>>
+    where Tz: TimeZone,
+          Tz::Offset: Copy
+{
+    /// Converts the times of the series to another zone
+    ///
+    /// Times keep the instant they represent, so the order of the series is unchanged.
+    ///
+    ///  * `zone` - Zone to convert the times to
+    ///  * return - Series with times in `zone`
+    ///
+    pub fn with_timezone<Tz2>(&self, zone: &Tz2) -> TimeSeries<ZonedDateTime<Tz2>>
+        where Tz2: TimeZone,
+              Tz2::Offset: Copy
+    {
+        // custom <fn time_series_with_timezone>
+        TimeSeries::from_sorted(self.iter()
+                                    .map(|tv| {
+                                             TimeValue {
+                                                 time: tv.time.with_timezone(zone),
+                                                 value: tv.value,
+                                             }
+                                         })
+                                    .collect())
+        // end <fn time_series_with_timezone>
+    }
+
+    /// Splits the series into the days of its local zone
+    ///
+    ///  * return - Each local date in the series with the range of its items
+    ///
+    pub fn local_day_ranges(&self) -> Vec<(Date, Range<usize>)> {
+        // custom <fn time_series_local_day_ranges>
+
+        let mut days: Vec<(Date, Range<usize>)> = Vec::new();
+        for (i, tv) in self.iter().enumerate() {
+            let date = tv.time.naive_local().date();
+            match days.last_mut() {
+                Some(&mut (last, ref mut range)) if last == date => range.end = i + 1,
+                _ => days.push((date, i..i + 1)),
+            }
+        }
+        days
+
+        // end <fn time_series_local_day_ranges>
+    }
+}
+
+// --- module function definitions ---
+
+// custom <module ModuleCodeBlock.moduleBottom>
+
+/// `time` moved onto `date` in its own zone, keeping its local time of day
+///
+/// An ambiguous local time takes the earlier instant and a local time skipped by a change
+/// of offset is moved forward across the gap.
+pub(crate) fn on_local_date<Tz>(time: &ZonedDateTime<Tz>, date: &Date) -> ZonedDateTime<Tz>
+    where Tz: TimeZone
+{
+    from_local(time, &date.and_time(time.naive_local().time()))
+}
+
+/// The instant in the zone of `time` showing `local`, resolved as by `on_local_date`
+pub(crate) fn from_local<Tz>(time: &ZonedDateTime<Tz>, local: &NaiveDateTime) -> ZonedDateTime<Tz>
+    where Tz: TimeZone
+{
+    let zone = time.timezone();
+    match zone.from_local_datetime(local) {
+        LocalResult::Single(result) |
+        LocalResult::Ambiguous(result, _) => result,
+        LocalResult::None => zone.from_utc_datetime(&(*local - time.offset().fix())),
+    }
+}
+
+// end <module ModuleCodeBlock.moduleBottom>
+
+/// Test module for zoned module
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Act365Fixed;
+    use DateTime;
+    use Tenor;
+    use Time;
+    use TimeSearchable;
+    use chrono::Utc;
+    use chrono_tz::America::New_York;
+    use chrono_tz::Europe::London;
+    use chrono_tz::Tz;
+
+    fn closes() -> TimeSeries<ZonedDateTime<Tz>> {
+        let close = |day, hour, value| {
+            TimeValue {
+                time: New_York.with_ymd_and_hms(2017, 3, day, hour, 0, 0).unwrap(),
+                value,
+            }
+        };
+        TimeSeries::try_from_vec(vec![close(10, 16, 1.0), close(10, 21, 2.0), close(13, 16, 3.0)])
+            .unwrap()
+    }
+
+    #[test]
+    fn with_timezone() {
+        let closes = closes();
+        let utc: TimeSeries<DateTime> = closes.with_timezone(&Utc);
+        assert_eq!(utc[0].time, Utc.with_ymd_and_hms(2017, 3, 10, 21, 0, 0).unwrap());
+        // New York moved to daylight time on 2017-03-12
+        assert_eq!(utc[2].time, Utc.with_ymd_and_hms(2017, 3, 13, 20, 0, 0).unwrap());
+        let london = utc.with_timezone(&London);
+        assert_eq!(london[2].time.naive_local().time(),
+                   chrono::NaiveTime::from_hms_opt(20, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn search_zoned() {
+        let closes = closes();
+        let noon = New_York.with_ymd_and_hms(2017, 3, 13, 12, 0, 0).unwrap();
+        assert_eq!(closes.before(noon), 0..2);
+        assert_eq!(closes.on_or_after(London.from_utc_datetime(&noon.naive_utc())
+                                          .with_timezone(&New_York)),
+                   2..3);
+    }
+
+    #[test]
+    fn local_day_ranges() {
+        let closes = closes();
+        let date = |day| Date::from_ymd_opt(2017, 3, day).unwrap();
+        assert_eq!(closes.local_day_ranges(), vec![(date(10), 0..2), (date(13), 2..3)]);
+        // In UTC the late trade on the 10th falls on the 11th
+        assert_eq!(closes.with_timezone(&Utc).local_day_ranges(),
+                   vec![(date(10), 0..1), (date(11), 1..2), (date(13), 2..3)]);
+    }
+
+    #[test]
+    fn local_calendar() {
+        let close = New_York.with_ymd_and_hms(2017, 3, 10, 16, 0, 0).unwrap();
+        let week_later = close + "1W".parse::<Tenor>().unwrap();
+        assert_eq!(week_later, New_York.with_ymd_and_hms(2017, 3, 17, 16, 0, 0).unwrap());
+        assert_relative_eq!(close.years_until_with(&week_later, &Act365Fixed), 7.0 / 365.0);
+
+        // 02:30 on 2017-03-12 does not exist in New York and moves across the gap
+        let early = New_York.with_ymd_and_hms(2017, 3, 11, 2, 30, 0).unwrap();
+        assert_eq!(early + "1D".parse::<Tenor>().unwrap(),
+                   New_York.with_ymd_and_hms(2017, 3, 12, 3, 30, 0).unwrap());
+    }
+}